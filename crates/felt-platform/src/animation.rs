@@ -0,0 +1,114 @@
+use std::time::{Duration, Instant};
+
+/// The shape of an animation's progress curve. Takes a linear `0.0..=1.0`
+/// time fraction and returns the eased `0.0..=1.0` progress.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// A cubic-bezier timing function, `(x1, y1, x2, y2)`, same convention as CSS.
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier_at(t, x1, y1, x2, y2),
+        }
+    }
+}
+
+/// Solve a CSS-style cubic-bezier timing function for the `y` at parametric
+/// `x == t`, via a fixed-iteration Newton-Raphson refinement on `x`.
+fn cubic_bezier_at(t: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
+    let bezier = |t: f64, a: f64, b: f64| {
+        let mt = 1.0 - t;
+        3.0 * mt * mt * t * a + 3.0 * mt * t * t * b + t * t * t
+    };
+
+    let mut u = t;
+    for _ in 0..8 {
+        let x = bezier(u, x1, x2) - t;
+        if x.abs() < 1e-6 {
+            break;
+        }
+        let dx = 3.0 * (1.0 - u).powi(2) * x1
+            + 6.0 * (1.0 - u) * u * (x2 - x1)
+            + 3.0 * u * u * (1.0 - x2);
+        if dx.abs() < 1e-6 {
+            break;
+        }
+        u -= x / dx;
+    }
+    bezier(u, y1, y2)
+}
+
+struct Animation {
+    start: Instant,
+    duration: Duration,
+    easing: Easing,
+    callback: Box<dyn FnMut(f32)>,
+}
+
+/// Registry of active time-based animations. While any animation is active
+/// the owning event loop should stay in `RedrawMode::Continuous`; once the
+/// last one completes, `tick` reports that and the caller can revert to
+/// `OnDemand`/`Wait`.
+#[derive(Default)]
+pub struct AnimationDriver {
+    animations: Vec<Animation>,
+}
+
+impl AnimationDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn a time-based animation: `callback` is invoked once per frame
+    /// with the eased `0.0..=1.0` progress until `duration` elapses.
+    pub fn spawn(
+        &mut self,
+        duration: Duration,
+        easing: Easing,
+        callback: impl FnMut(f32) + 'static,
+    ) {
+        self.animations.push(Animation {
+            start: Instant::now(),
+            duration,
+            easing,
+            callback: Box::new(callback),
+        });
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    /// Advance every active animation by one frame, dropping those that have
+    /// completed (after a final callback at progress `1.0`). Returns whether
+    /// any animation is still running.
+    pub fn tick(&mut self) -> bool {
+        let now = Instant::now();
+        self.animations.retain_mut(|anim| {
+            let elapsed = now.duration_since(anim.start).as_secs_f64();
+            let t = (elapsed / anim.duration.as_secs_f64().max(f64::EPSILON)).min(1.0);
+            (anim.callback)(anim.easing.apply(t) as f32);
+            t < 1.0
+        });
+        self.is_active()
+    }
+}
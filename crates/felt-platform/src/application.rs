@@ -1,9 +1,12 @@
+use crate::animation::{AnimationDriver, Easing};
 use crate::renderer::{Renderer, RendererOptions};
 use crate::size::Size;
 use std::sync::Arc;
+use std::time::Duration;
 use winit::application::ApplicationHandler;
-use winit::event::WindowEvent;
+use winit::event::{ElementState, MouseScrollDelta, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::keyboard::{KeyCode, PhysicalKey};
 use winit::window::{Window, WindowId};
 
 /// Controls when the window is redrawn.
@@ -23,12 +26,25 @@ pub enum RedrawMode {
 
 type InitCallback = dyn for<'a> FnOnce(&mut AppContext<'a>);
 
+/// A window host built around [`Renderer`]'s stats overlay and DevTools
+/// toggles (F1-F4: vsync, AA config, min/max reset) rather than a widget
+/// tree — it predates, and is deliberately kept separate from,
+/// `felt_platform::App` (see the crate-level docs in `lib.rs`). Suspend and
+/// resume are handled here against `Renderer` directly rather than through
+/// `App`'s `RenderState`, since this struct doesn't share `App`'s surface
+/// lifecycle plumbing. Used by the benchmark/example binaries under
+/// `examples/`; new widget-tree-hosted applications should use `App`
+/// instead.
 #[derive(Default)]
 pub struct Application {
     window: Option<Arc<Window>>,
     renderer: Option<Renderer>,
     init: Option<Box<InitCallback>>,
     redraw_mode: RedrawMode,
+    /// The redraw mode requested by `init`/widgets, independent of whatever
+    /// the animation driver is temporarily forcing.
+    base_redraw_mode: RedrawMode,
+    animations: AnimationDriver,
 }
 
 impl Application {
@@ -38,6 +54,8 @@ impl Application {
             renderer: None,
             init: None,
             redraw_mode: RedrawMode::default(),
+            base_redraw_mode: RedrawMode::default(),
+            animations: AnimationDriver::new(),
         }
     }
 
@@ -59,16 +77,45 @@ impl Application {
     /// Common use case: switch to Continuous during animations, back to OnDemand when idle.
     pub fn set_redraw_mode(&mut self, event_loop: &ActiveEventLoop, redraw_mode: RedrawMode) {
         self.redraw_mode = redraw_mode;
+        self.base_redraw_mode = redraw_mode;
         let control_flow = match redraw_mode {
             RedrawMode::OnDemand => ControlFlow::Wait,
             RedrawMode::Continuous => ControlFlow::Poll,
         };
         event_loop.set_control_flow(control_flow);
     }
+
+    /// Spawn a time-based animation that drives per-frame redraws on its own,
+    /// without the caller needing to manually force `RedrawMode::Continuous`.
+    /// The event loop switches to `Continuous` while any animation is active
+    /// and automatically reverts to the base redraw mode once the last one
+    /// finishes.
+    pub fn spawn_animation(
+        &mut self,
+        duration: Duration,
+        easing: Easing,
+        callback: impl FnMut(f32) + 'static,
+    ) {
+        self.animations.spawn(duration, easing, callback);
+    }
 }
 
 impl ApplicationHandler for Application {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        // Resuming after a `suspended()` on a platform like Android, where
+        // the native window survives but its surface handle doesn't: just
+        // recreate the surface against the window we already have.
+        if let (Some(window), Some(renderer)) = (&self.window, &mut self.renderer)
+            && renderer.is_suspended()
+        {
+            if let Err(e) = pollster::block_on(renderer.resume(Arc::clone(window))) {
+                eprintln!("Failed to resume renderer: {}", e);
+                return;
+            }
+            window.request_redraw();
+            return;
+        }
+
         if self.window.is_none() {
             let mut cx = AppContext::new(event_loop);
 
@@ -94,6 +141,7 @@ impl ApplicationHandler for Application {
                         event_loop.set_control_flow(control_flow);
                         self.renderer = Some(renderer);
                         self.redraw_mode = redraw_mode;
+                        self.base_redraw_mode = redraw_mode;
                     }
                     Err(e) => {
                         eprintln!("Failed to initialize renderer: {}", e);
@@ -108,6 +156,12 @@ impl ApplicationHandler for Application {
         }
     }
 
+    fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(renderer) = &mut self.renderer {
+            renderer.suspend();
+        }
+    }
+
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         match event {
             WindowEvent::CloseRequested => {
@@ -119,6 +173,26 @@ impl ApplicationHandler for Application {
                 {
                     eprintln!("Render error: {}", e);
                 }
+
+                let animating = self.animations.tick();
+                let desired_mode = if animating {
+                    RedrawMode::Continuous
+                } else {
+                    self.base_redraw_mode
+                };
+                if desired_mode != self.redraw_mode {
+                    self.redraw_mode = desired_mode;
+                    let control_flow = match desired_mode {
+                        RedrawMode::OnDemand => ControlFlow::Wait,
+                        RedrawMode::Continuous => ControlFlow::Poll,
+                    };
+                    event_loop.set_control_flow(control_flow);
+                }
+                if animating {
+                    if let Some(window) = &self.window {
+                        window.request_redraw();
+                    }
+                }
             }
             WindowEvent::Resized(new_size) => {
                 if let Some(renderer) = &mut self.renderer {
@@ -133,6 +207,40 @@ impl ApplicationHandler for Application {
                     renderer.set_scale_factor(scale_factor);
                 }
             }
+            // DevTools-style overlay toggles, following the Vello winit
+            // example's convention of driving performance-inspection tools
+            // straight off function keys: F1 show/hide the stats overlay,
+            // F2 reset its tracked min/max, F3 cycle vsync, F4 cycle AA.
+            WindowEvent::KeyboardInput { event, .. } => {
+                if event.state == ElementState::Pressed
+                    && !event.repeat
+                    && let PhysicalKey::Code(code) = event.physical_key
+                    && let Some(renderer) = &mut self.renderer
+                {
+                    match code {
+                        KeyCode::F1 => renderer.toggle_stats(),
+                        KeyCode::F2 => renderer.clear_min_and_max(),
+                        KeyCode::F3 => renderer.cycle_vsync(),
+                        KeyCode::F4 => renderer.cycle_aa_config(),
+                        _ => {}
+                    }
+                    if let Some(window) = &self.window {
+                        window.request_redraw();
+                    }
+                }
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                if let Some(renderer) = &mut self.renderer {
+                    let (dx, dy) = match delta {
+                        MouseScrollDelta::LineDelta(x, y) => (x as f64 * 20.0, y as f64 * 20.0),
+                        MouseScrollDelta::PixelDelta(pos) => (pos.x, pos.y),
+                    };
+                    renderer.accumulate_scroll((dx, dy));
+                }
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
+            }
             _ => {}
         }
     }
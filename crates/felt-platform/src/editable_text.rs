@@ -0,0 +1,242 @@
+//! An editable text buffer: shaping is delegated to [`SimpleText`], while
+//! caret placement, selection, and cluster-aware movement live here. See
+//! `App::mount_editable_text`, which forwards pointer/keyboard
+//! `WindowEvent`s into one of these — the same hit-test/drag/arrow-key
+//! bookkeeping parley's own `editor` example implements by hand.
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use vello::Scene;
+use vello::kurbo::{Affine, Point, Rect};
+use vello::peniko::Brush;
+
+use crate::simple_text::{SimpleText, TextLayout};
+
+/// Where [`TextEditor::move_caret`] should land, relative to the current
+/// caret — one grapheme cluster at a time rather than one `char`, so
+/// combining marks and multi-codepoint emoji move as a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaretMovement {
+    Left,
+    Right,
+    Home,
+    End,
+}
+
+pub struct TextEditor {
+    text: String,
+    size: f32,
+    brush: Brush,
+    max_width: Option<f32>,
+    /// Built lazily (and rebuilt whenever `dirty`) rather than at
+    /// construction time, since the caller may not have a [`SimpleText`]
+    /// (which needs a `wgpu::Device`) on hand yet — see
+    /// [`Self::ensure_layout`].
+    layout: Option<TextLayout>,
+    dirty: bool,
+    /// Byte offset the selection was started from; equal to `caret` when
+    /// there's no selection.
+    anchor: usize,
+    caret: usize,
+    /// Set between a button-down hit-test and the matching button-up, so
+    /// pointer moves in between extend the selection instead of being
+    /// ignored.
+    dragging: bool,
+}
+
+impl TextEditor {
+    pub fn new(text: impl Into<String>, size: f32, brush: Brush, max_width: Option<f32>) -> Self {
+        Self {
+            text: text.into(),
+            size,
+            brush,
+            max_width,
+            layout: None,
+            dirty: true,
+            anchor: 0,
+            caret: 0,
+            dragging: false,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// `[start, end)` byte range of the current selection, or `None` if the
+    /// caret and anchor coincide (no selection).
+    pub fn selection(&self) -> Option<Range<usize>> {
+        (self.anchor != self.caret)
+            .then(|| self.anchor.min(self.caret)..self.anchor.max(self.caret))
+    }
+
+    /// Re-shape against the current text if it's changed since the layout
+    /// was last built (or it's never been built), then return it. Callers
+    /// that only read the layout (drawing, hit-testing from outside) should
+    /// call this rather than relying on a layout from a stale `insert_char`/
+    /// `backspace` call.
+    pub fn ensure_layout(&mut self, simple_text: &mut SimpleText) -> &TextLayout {
+        if self.dirty || self.layout.is_none() {
+            self.layout = Some(simple_text.layout(&self.text, self.size, self.brush.clone(), self.max_width));
+            self.dirty = false;
+        }
+        self.layout.as_ref().unwrap()
+    }
+
+    /// The caret's rectangle in local layout space, for drawing a blinking
+    /// cursor — `None` before the first [`Self::ensure_layout`] call.
+    pub fn caret_rect(&self) -> Option<Rect> {
+        self.layout.as_ref().map(|layout| layout.cursor_rect(self.caret))
+    }
+
+    /// One highlight rect per visual line the selection spans, in local
+    /// layout space. Adjacent cluster-boundary caret rects on the same line
+    /// (same `y0`/`y1`) are unioned together; a cross-line selection ends up
+    /// as one rect per line, which is all a single-line editor ever needs
+    /// and is a reasonable approximation for a wrapped one.
+    pub fn selection_rects(&self) -> Vec<Rect> {
+        let (Some(range), Some(layout)) = (self.selection(), &self.layout) else {
+            return Vec::new();
+        };
+
+        let mut rects: Vec<Rect> = Vec::new();
+        for offset in cluster_boundaries(&self.text, range) {
+            let rect = layout.cursor_rect(offset);
+            match rects
+                .iter_mut()
+                .find(|existing| existing.y0 == rect.y0 && existing.y1 == rect.y1)
+            {
+                Some(existing) => *existing = existing.union(rect),
+                None => rects.push(rect),
+            }
+        }
+        rects
+    }
+
+    /// Place the caret (collapsing any selection) at the nearest cluster
+    /// boundary to `point` in local layout space, and start tracking a drag
+    /// — e.g. on pointer-down. A no-op before the first [`Self::ensure_layout`].
+    pub fn set_caret_from_point(&mut self, point: Point) {
+        let Some(layout) = &self.layout else {
+            return;
+        };
+        let offset = layout.hit_test_point(point.x as f32, point.y as f32);
+        self.caret = offset;
+        self.anchor = offset;
+        self.dragging = true;
+    }
+
+    /// Extend the selection to `point` while the pointer is held down (see
+    /// [`Self::set_caret_from_point`]); a no-op if no drag is in progress.
+    pub fn extend_selection_to_point(&mut self, point: Point) {
+        if !self.dragging {
+            return;
+        }
+        if let Some(layout) = &self.layout {
+            self.caret = layout.hit_test_point(point.x as f32, point.y as f32);
+        }
+    }
+
+    /// End the drag started by [`Self::set_caret_from_point`] — e.g. on
+    /// pointer-up.
+    pub fn end_drag(&mut self) {
+        self.dragging = false;
+    }
+
+    /// Move the caret by one grapheme cluster, or to the start/end of the
+    /// buffer, clamping at either edge. `extend` keeps the anchor in place
+    /// (shift-click/shift-arrow selection); otherwise the anchor follows the
+    /// caret, collapsing any selection.
+    pub fn move_caret(&mut self, movement: CaretMovement, extend: bool) {
+        let clusters = cluster_boundaries(&self.text, 0..self.text.len());
+        let pos = clusters.iter().position(|&i| i == self.caret).unwrap_or(0);
+
+        self.caret = match movement {
+            CaretMovement::Left => clusters[pos.saturating_sub(1)],
+            CaretMovement::Right => clusters[(pos + 1).min(clusters.len() - 1)],
+            CaretMovement::Home => 0,
+            CaretMovement::End => self.text.len(),
+        };
+        if !extend {
+            self.anchor = self.caret;
+        }
+    }
+
+    /// Insert `ch` at the caret, replacing the selection if there is one.
+    /// Marks the layout dirty rather than re-shaping immediately — the next
+    /// [`Self::ensure_layout`] call picks it up.
+    pub fn insert_char(&mut self, ch: char) {
+        let mut buf = [0u8; 4];
+        self.replace_selection(ch.encode_utf8(&mut buf));
+    }
+
+    /// Delete one grapheme cluster before the caret (or the selection, if
+    /// there is one).
+    pub fn backspace(&mut self) {
+        if self.selection().is_some() {
+            self.replace_selection("");
+            return;
+        }
+        // `cluster_boundaries` always appends `self.caret` itself as a
+        // trailing sentinel, so the boundary we actually want is the one
+        // before that, not the last one.
+        let before = cluster_boundaries(&self.text, 0..self.caret)
+            .into_iter()
+            .rev()
+            .nth(1)
+            .unwrap_or(0);
+        self.text.replace_range(before..self.caret, "");
+        self.caret = before;
+        self.anchor = before;
+        self.dirty = true;
+    }
+
+    fn replace_selection(&mut self, with: &str) {
+        let range = self.selection().unwrap_or(self.caret..self.caret);
+        self.text.replace_range(range.clone(), with);
+        self.caret = range.start + with.len();
+        self.anchor = self.caret;
+        self.dirty = true;
+    }
+
+    /// Draw the shaped text, selection highlight, and caret into `scene`,
+    /// positioned by `transform`. Re-shapes first if the text has changed
+    /// since the last call (see [`Self::ensure_layout`]).
+    pub fn draw(
+        &mut self,
+        simple_text: &mut SimpleText,
+        scene: &mut Scene,
+        transform: Affine,
+        scale_factor: f64,
+        queue: &wgpu::Queue,
+        selection_brush: &Brush,
+        caret_brush: &Brush,
+    ) {
+        self.ensure_layout(simple_text);
+
+        for rect in self.selection_rects() {
+            scene.fill(
+                vello::peniko::Fill::NonZero,
+                transform,
+                selection_brush,
+                None,
+                &rect,
+            );
+        }
+
+        simple_text.draw_layout(scene, self.layout.as_ref().unwrap(), transform, scale_factor, queue);
+
+        if let Some(caret) = self.caret_rect() {
+            scene.fill(vello::peniko::Fill::NonZero, transform, caret_brush, None, &caret);
+        }
+    }
+}
+
+/// Byte offsets of every grapheme cluster boundary within `range` of `text`
+/// (relative to the start of `text`, not `range`), including `range.end`.
+fn cluster_boundaries(text: &str, range: Range<usize>) -> Vec<usize> {
+    text[range.clone()]
+        .grapheme_indices(true)
+        .map(|(i, _)| range.start + i)
+        .chain(std::iter::once(range.end))
+        .collect()
+}
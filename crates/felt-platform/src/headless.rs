@@ -0,0 +1,163 @@
+//! Offscreen rendering with no window/surface, used by reftest harnesses and
+//! anywhere else a scene needs to become pixels without presenting to a
+//! display. A separate type from [`crate::renderer::Renderer`] rather than an
+//! `Option<surface>` branch through it, mirroring how `App`/`Application`
+//! already exist as two independent entry points for two different contexts.
+use vello::util::RenderContext;
+
+use crate::renderer::RendererError;
+
+/// Tightly packed RGBA8 pixels, row-major, with no row padding.
+pub struct RenderedImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+pub struct HeadlessRenderer {
+    context: RenderContext,
+    dev_id: usize,
+    vello_renderer: vello::Renderer,
+}
+
+impl HeadlessRenderer {
+    pub async fn new() -> Result<Self, RendererError> {
+        let mut context = RenderContext::new();
+        let dev_id = context.device(None).await.ok_or(RendererError::NoDevice)?;
+        let device_handle = &context.devices[dev_id];
+        let vello_renderer =
+            vello::Renderer::new(&device_handle.device, vello::RendererOptions::default())?;
+
+        Ok(Self {
+            context,
+            dev_id,
+            vello_renderer,
+        })
+    }
+
+    /// Render `scene` into an offscreen `width`x`height` texture and read the
+    /// result back to CPU memory as RGBA8.
+    pub fn render_to_image(
+        &mut self,
+        scene: &vello::Scene,
+        width: u32,
+        height: u32,
+        base_color: vello::peniko::Color,
+    ) -> Result<RenderedImage, RendererError> {
+        let device_handle = &self.context.devices[self.dev_id];
+        let data = render_scene_to_rgba8(
+            &device_handle.device,
+            &device_handle.queue,
+            &mut self.vello_renderer,
+            scene,
+            width,
+            height,
+            base_color,
+        )?;
+
+        Ok(RenderedImage {
+            width,
+            height,
+            data,
+        })
+    }
+}
+
+/// Render `scene` into an offscreen `width`x`height` texture on `device`/
+/// `queue` via `vello_renderer`, reading the result back to CPU memory as
+/// tightly packed (no row padding) RGBA8. Shared by
+/// [`HeadlessRenderer::render_to_image`] and
+/// [`crate::LayerRenderHandle::render_layer_to_rgba8`] — the two places a
+/// scene needs to become pixels without ever touching a live window surface.
+pub(crate) fn render_scene_to_rgba8(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    vello_renderer: &mut vello::Renderer,
+    scene: &vello::Scene,
+    width: u32,
+    height: u32,
+    base_color: vello::peniko::Color,
+) -> Result<Vec<u8>, RendererError> {
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("felt-platform headless target"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::STORAGE_BINDING
+            | wgpu::TextureUsages::COPY_SRC
+            | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let target_view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let render_params = vello::RenderParams {
+        base_color,
+        width,
+        height,
+        antialiasing_method: vello::AaConfig::Area,
+    };
+
+    vello_renderer.render_to_texture(device, queue, scene, &target_view, &render_params)?;
+
+    // wgpu requires buffer-mapped texture copies to pad each row up to
+    // `COPY_BYTES_PER_ROW_ALIGNMENT`; we strip the padding back out below.
+    let unpadded_bytes_per_row = width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+    let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("felt-platform headless readback"),
+        size: (padded_bytes_per_row * height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("felt-platform headless copy"),
+    });
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &readback_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit([encoder.finish()]);
+
+    let slice = readback_buffer.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    rx.recv()
+        .map_err(|_| RendererError::MapFailed)?
+        .map_err(|_| RendererError::MapFailed)?;
+
+    let mut data = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+    {
+        let mapped = slice.get_mapped_range();
+        for row in 0..height as usize {
+            let start = row * padded_bytes_per_row as usize;
+            data.extend_from_slice(&mapped[start..start + unpadded_bytes_per_row as usize]);
+        }
+    }
+    readback_buffer.unmap();
+
+    Ok(data)
+}
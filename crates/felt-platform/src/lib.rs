@@ -1,16 +1,255 @@
+//! Two windowing/rendering entry points live in this crate, and they are
+//! *not* interchangeable:
+//!
+//! - [`App`] (this file) is the canonical, actively developed one. It owns
+//!   the widget-tree integration (`felt_ui::AppExtension::mount_ui`, used by
+//!   `demo`), damage-tracked repainting, cached offscreen layers, headless
+//!   `render_to_image`, SVG loading, and editable text. New work should
+//!   build on this.
+//! - [`application::Application`] + [`renderer::Renderer`] is the earlier
+//!   stack, kept around for its DevTools overlay (stats, GPU timing,
+//!   vsync/AA toggles via F1-F4) and the benchmark/example binaries under
+//!   `examples/` that exercise it. It has its own, separately implemented
+//!   suspend/resume handling rather than sharing `App`'s `RenderState`.
+//!
+//! These grew independently because they serve different purposes (a
+//! widget-tree host vs. a raw-rendering benchmarking harness) rather than by
+//! accident, but the duplication — two suspend/resume implementations, two
+//! present-mode/antialiasing configs, two event loops — is real and ought to
+//! be reconciled (most likely by rebuilding the DevTools overlay as an `App`
+//! layer and retiring `application`/`renderer` as the window-owning entry
+//! point) rather than grown further in parallel.
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use vello::util::{RenderContext, RenderSurface};
 use vello::{Renderer, Scene};
-use winit::event::{Event, WindowEvent};
-use winit::event_loop::{ControlFlow, EventLoop};
-use winit::window::Window;
+use winit::application::ApplicationHandler;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::window::{Window, WindowId};
+
+pub mod animation;
+pub mod application;
+pub mod editable_text;
+pub mod glyph_atlas;
+pub mod headless;
+pub mod renderer;
+pub mod simple_text;
+pub mod stats;
+pub mod svg;
+
+pub use editable_text::{CaretMovement, TextEditor};
+pub use svg::{SvgDocument, SvgError};
+
+/// Which physical mouse button an [`InputEvent::PointerDown`]/[`InputEvent::PointerUp`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+fn translate_mouse_button(button: MouseButton) -> PointerButton {
+    match button {
+        MouseButton::Left => PointerButton::Left,
+        MouseButton::Right => PointerButton::Right,
+        MouseButton::Middle => PointerButton::Middle,
+        MouseButton::Other(code) => PointerButton::Other(code),
+        _ => PointerButton::Other(0),
+    }
+}
+
+/// Raw pointer/scroll input delivered to an [`App::on_input`] callback,
+/// already stripped of winit's types so crates downstream of `felt-platform`
+/// (e.g. `felt-ui`'s hit-testing) don't need a `winit` dependency of their
+/// own. Positions are in physical pixels relative to the window's top-left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent {
+    PointerMoved {
+        x: f64,
+        y: f64,
+    },
+    PointerDown {
+        x: f64,
+        y: f64,
+        button: PointerButton,
+    },
+    PointerUp {
+        x: f64,
+        y: f64,
+        button: PointerButton,
+    },
+    /// Wheel/trackpad scroll, carrying the last known cursor position since
+    /// winit's `MouseWheel` event doesn't include one.
+    Wheel {
+        x: f64,
+        y: f64,
+        delta_x: f64,
+        delta_y: f64,
+    },
+}
+
+/// Which keyboard modifier keys are currently held, tracked from raw
+/// `WindowEvent::KeyboardInput` presses/releases rather than winit's own
+/// `Modifiers` type, keeping `felt-platform`'s public surface winit-free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    /// Update `self` from a single physical key's new pressed state; returns
+    /// `true` if `code` was a modifier key (and `self` was updated), `false`
+    /// if it was some other key this doesn't track.
+    fn update(&mut self, code: KeyCode, pressed: bool) -> bool {
+        let field = match code {
+            KeyCode::ShiftLeft | KeyCode::ShiftRight => &mut self.shift,
+            KeyCode::ControlLeft | KeyCode::ControlRight => &mut self.control,
+            KeyCode::AltLeft | KeyCode::AltRight => &mut self.alt,
+            KeyCode::SuperLeft | KeyCode::SuperRight => &mut self.meta,
+            _ => return false,
+        };
+        *field = pressed;
+        true
+    }
+}
+
+/// Aggregated input and timing state handed to an [`App::mount_with`]
+/// callback once per [`WindowEvent::RedrawRequested`] — everything a scene
+/// needs to react to the pointer/keyboard or drive time-based animation
+/// without the caller wiring up a separate [`App::on_input`] callback per
+/// event kind. `scroll_delta` is the sum of every `MouseWheel` event since
+/// the previous frame (and resets to zero every frame); everything else is
+/// a live snapshot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameContext {
+    pub cursor_position: (f64, f64),
+    pub pointer_buttons: PointerButtons,
+    pub scroll_delta: (f64, f64),
+    pub modifiers: Modifiers,
+    /// Wall-clock time since the previous `RedrawRequested`, or `Duration::ZERO`
+    /// for the very first frame (there's nothing to measure it against).
+    pub elapsed: Duration,
+}
+
+/// Which pointer buttons are currently held down. A fixed `Left`/`Right`/
+/// `Middle` trio covers the overwhelming majority of input devices without
+/// the allocation a `Vec<PointerButton>` would need every frame; `Other`
+/// buttons are rare enough (and rarely need held-state, as opposed to the
+/// edge-triggered [`InputEvent::PointerDown`]/[`PointerUp`]) not to bother.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PointerButtons {
+    pub left: bool,
+    pub right: bool,
+    pub middle: bool,
+}
+
+impl PointerButtons {
+    fn set(&mut self, button: PointerButton, pressed: bool) {
+        match button {
+            PointerButton::Left => self.left = pressed,
+            PointerButton::Right => self.right = pressed,
+            PointerButton::Middle => self.middle = pressed,
+            PointerButton::Other(_) => {}
+        }
+    }
+}
+
+/// A cloneable handle that can render a [`vello::Scene`] into an offscreen
+/// RGBA8 image, independent of `App` itself. `App` can't simply lend out
+/// `&mut self` to its own paint callback (the callback is a closure *stored
+/// on* `App`, already being called through a borrow of it), so this carries
+/// just what offscreen rendering needs — `wgpu::Device`/`wgpu::Queue` are
+/// cheap to clone (`Arc`-backed internally), and the renderer itself sits
+/// behind an `Rc<RefCell<_>>` so every handle cloned from the same `App`
+/// shares it rather than each compiling its own shaders.
+#[derive(Clone)]
+pub struct LayerRenderHandle {
+    device: vello::wgpu::Device,
+    queue: vello::wgpu::Queue,
+    renderer: Rc<RefCell<Renderer>>,
+}
+
+impl LayerRenderHandle {
+    /// Render `scene` into a `width`x`height` texture and read it back as
+    /// tightly packed (no row padding) RGBA8.
+    pub fn render_layer_to_rgba8(&self, scene: &vello::Scene, width: u32, height: u32) -> Option<Vec<u8>> {
+        crate::headless::render_scene_to_rgba8(
+            &self.device,
+            &self.queue,
+            &mut self.renderer.borrow_mut(),
+            scene,
+            width,
+            height,
+            vello::peniko::Color::TRANSPARENT,
+        )
+        .ok()
+    }
+}
+
+/// Where the GPU surface stands relative to the window's lifecycle. On
+/// Android (and increasingly the web) the native window handle can outlive
+/// the surface built against it — backgrounding the app invalidates the
+/// surface but `onResume` hands back the *same* window, so there's no need
+/// to rebuild it from scratch. Matches `Application`/`Renderer`'s
+/// suspend/resume split in `application.rs`/`renderer.rs`.
+enum RenderState {
+    /// No live surface. Carries the window so it can be reused verbatim on
+    /// the next `resumed()`, or `None` before the very first one.
+    Suspended(Option<Arc<Window>>),
+    Active {
+        surface: RenderSurface<'static>,
+        window: Arc<Window>,
+    },
+}
 
 pub struct App {
     context: RenderContext,
     renderers: Vec<Option<Renderer>>,
-    surface: Option<RenderSurface<'static>>,
-    window: Option<Arc<Window>>,
-    paint_callback: Option<Box<dyn FnMut(&mut vello::Scene, u32, u32)>>,
+    render_state: RenderState,
+    paint_callback:
+        Option<Box<dyn FnMut(&mut vello::Scene, u32, u32, Option<LayerRenderHandle>) -> bool>>,
+    frame_callback: Option<
+        Box<dyn FnMut(&mut vello::Scene, u32, u32, Option<LayerRenderHandle>, &FrameContext) -> bool>,
+    >,
+    input_callback: Option<Box<dyn FnMut(InputEvent)>>,
+    cursor_position: (f64, f64),
+    /// Accumulated since the last `RedrawRequested`, for [`Self::mount_with`]
+    /// callbacks — reset to zero every frame it's handed out.
+    scroll_accum: (f64, f64),
+    pointer_buttons: PointerButtons,
+    modifiers: Modifiers,
+    /// `None` until the first `RedrawRequested`, so that frame's `elapsed`
+    /// reads as zero rather than measuring against app startup.
+    last_frame_instant: Option<Instant>,
+    /// Separate from `renderers` (which is configured with the live
+    /// surface's format for presenting): offscreen layer rendering targets a
+    /// plain `Rgba8Unorm` texture, never the surface, so it gets its own
+    /// lazily-created renderer rather than risking the surface-presenting
+    /// one being reconfigured out from under it.
+    layer_renderer: Option<Rc<RefCell<Renderer>>>,
+    /// Set by [`Self::mount_editable_text`]; pointer/keyboard input is
+    /// forwarded into it (translated into its local layout space by
+    /// `editable_text_origin`) and it's drawn every frame in
+    /// `RedrawRequested`.
+    editable_text: Option<Rc<RefCell<TextEditor>>>,
+    editable_text_origin: vello::kurbo::Point,
+    /// Lazily created (needs a `wgpu::Device`) the first time an editable
+    /// buffer is actually drawn, mirroring `layer_renderer`.
+    text_renderer: Option<simple_text::SimpleText>,
+    /// Only takes effect on the next `create_surface` (i.e. the next
+    /// `resumed`) unless changed via [`Self::set_present_mode`], which
+    /// reconfigures a live surface immediately.
+    present_mode: vello::wgpu::PresentMode,
+    base_color: vello::peniko::Color,
+    antialiasing: vello::AaConfig,
 }
 
 impl App {
@@ -18,120 +257,556 @@ impl App {
         Self {
             context: RenderContext::new(),
             renderers: vec![],
-            surface: None,
-            window: None,
+            render_state: RenderState::Suspended(None),
             paint_callback: None,
+            frame_callback: None,
+            input_callback: None,
+            cursor_position: (0.0, 0.0),
+            scroll_accum: (0.0, 0.0),
+            pointer_buttons: PointerButtons::default(),
+            modifiers: Modifiers::default(),
+            last_frame_instant: None,
+            layer_renderer: None,
+            editable_text: None,
+            editable_text_origin: vello::kurbo::Point::ORIGIN,
+            text_renderer: None,
+            present_mode: vello::wgpu::PresentMode::AutoVsync,
+            base_color: vello::peniko::Color::BLACK,
+            antialiasing: vello::AaConfig::Area,
+        }
+    }
+
+    /// Surface present mode used the next time a surface is created (i.e.
+    /// the next `resumed`) — see [`Self::set_present_mode`] to change it on
+    /// an already-live surface. `AutoVsync` (the default) falls back to
+    /// `Fifo` where the platform doesn't support it.
+    pub fn with_present_mode(mut self, present_mode: vello::wgpu::PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
+    /// Color the surface is cleared to wherever the scene doesn't draw
+    /// anything opaque. Defaults to black.
+    pub fn with_base_color(mut self, base_color: vello::peniko::Color) -> Self {
+        self.base_color = base_color;
+        self
+    }
+
+    /// Antialiasing method used when rendering each frame: `Area` (vello's
+    /// analytical default), or `Msaa8`/`Msaa16` for hardware multisampling.
+    /// Defaults to `Area`.
+    pub fn with_antialiasing(mut self, antialiasing: vello::AaConfig) -> Self {
+        self.antialiasing = antialiasing;
+        self
+    }
+
+    pub fn present_mode(&self) -> vello::wgpu::PresentMode {
+        self.present_mode
+    }
+
+    pub fn base_color(&self) -> vello::peniko::Color {
+        self.base_color
+    }
+
+    pub fn antialiasing(&self) -> vello::AaConfig {
+        self.antialiasing
+    }
+
+    /// Change the antialiasing method at runtime, e.g. from a DevTools-style
+    /// keybinding — takes effect on the next `RedrawRequested`, no surface
+    /// reconfiguration needed.
+    pub fn set_antialiasing(&mut self, antialiasing: vello::AaConfig) {
+        self.antialiasing = antialiasing;
+    }
+
+    /// Change the base clear color at runtime — takes effect on the next
+    /// `RedrawRequested`.
+    pub fn set_base_color(&mut self, base_color: vello::peniko::Color) {
+        self.base_color = base_color;
+    }
+
+    /// Change the present mode at runtime, reconfiguring a live surface
+    /// immediately (e.g. switching to `Immediate` for unlimited-FPS
+    /// benchmarking without tearing down and recreating the window).
+    pub fn set_present_mode(&mut self, present_mode: vello::wgpu::PresentMode) {
+        self.present_mode = present_mode;
+
+        if let RenderState::Active { surface, .. } = &mut self.render_state {
+            surface.config.present_mode = present_mode;
+            surface
+                .surface
+                .configure(&self.context.devices[surface.dev_id].device, &surface.config);
         }
     }
 
-    pub fn mount(&mut self, callback: impl FnMut(&mut vello::Scene, u32, u32) + 'static) {
+    /// `callback` is invoked once per frame with the scene to fill in, the
+    /// current window size, and (once a GPU device has been acquired) a
+    /// [`LayerRenderHandle`] it can use to rasterize a cacheable subtree into
+    /// its own offscreen image instead of re-encoding it into `scene` every
+    /// time. Its `bool` return says whether anything changed since last
+    /// frame: `true` presents this frame and schedules another; `false`
+    /// skips both, letting the GPU idle until the next input event (or
+    /// resize) requests a redraw.
+    ///
+    /// See [`Self::mount_with`] for a variant that also hands the callback
+    /// accumulated pointer/keyboard state and frame timing.
+    pub fn mount(
+        &mut self,
+        callback: impl FnMut(&mut vello::Scene, u32, u32, Option<LayerRenderHandle>) -> bool
+        + 'static,
+    ) {
         self.paint_callback = Some(Box::new(callback));
     }
 
+    /// Like [`Self::mount`], but `callback` also receives a [`FrameContext`]
+    /// snapshotting cursor position, held pointer buttons, modifier keys,
+    /// scroll delta accumulated since the previous frame, and elapsed time —
+    /// enough to drive interactive or time-based scenes (panning/zooming on
+    /// scroll, hit-testing on click, animation) without a separate
+    /// `on_input` callback for each event kind. Mutually exclusive with
+    /// `mount`: whichever was called last wins.
+    pub fn mount_with(
+        &mut self,
+        callback: impl FnMut(&mut vello::Scene, u32, u32, Option<LayerRenderHandle>, &FrameContext) -> bool
+        + 'static,
+    ) {
+        self.frame_callback = Some(Box::new(callback));
+    }
+
+    /// Register a callback invoked with every pointer/wheel [`InputEvent`]
+    /// this window receives, e.g. to dispatch hit-tested events into a
+    /// `felt-ui` widget tree.
+    pub fn on_input(&mut self, callback: impl FnMut(InputEvent) + 'static) {
+        self.input_callback = Some(Box::new(callback));
+    }
+
+    /// `mount` a static [`SvgDocument`], scaled (preserving aspect ratio)
+    /// and centered to fit the window every frame via
+    /// [`SvgDocument::fit_transform`] — the "load this icon/illustration and
+    /// show it" path, without hand-writing kurbo geometry or wiring up a
+    /// `Resized` handler (the fit is just recomputed from the current window
+    /// size passed into the paint callback, which already changes on
+    /// resize). For anything beyond a single static document — layering SVGs
+    /// with other content, or animating the transform — use
+    /// [`SvgDocument::append_to`] directly from a [`Self::mount`] callback
+    /// instead.
+    pub fn mount_svg(&mut self, document: SvgDocument) {
+        self.mount(move |scene, width, height, _layer_renderer| {
+            let viewport = vello::kurbo::Size::new(width as f64, height as f64);
+            document.append_to(scene, document.fit_transform(viewport));
+            true
+        });
+    }
+
+    /// Mount a single editable text buffer at `origin` (in window space).
+    /// From here on `App` forwards pointer clicks/drags and arrow/Home/End/
+    /// Backspace/character key presses into the returned [`TextEditor`]
+    /// (translating pointer positions into its local layout space by
+    /// subtracting `origin`), and draws its shaped text, selection
+    /// highlight, and caret every frame. Only one editable buffer is
+    /// supported at a time — mounting a new one replaces the last.
+    pub fn mount_editable_text(
+        &mut self,
+        text: impl Into<String>,
+        size: f32,
+        brush: vello::peniko::Brush,
+        max_width: Option<f32>,
+        origin: vello::kurbo::Point,
+    ) -> Rc<RefCell<TextEditor>> {
+        let editor = Rc::new(RefCell::new(TextEditor::new(text, size, brush, max_width)));
+        self.editable_text = Some(Rc::clone(&editor));
+        self.editable_text_origin = origin;
+        editor
+    }
+
     pub fn run(mut self) {
         let event_loop = EventLoop::new().unwrap();
-        let window = Arc::new(
-            winit::window::WindowBuilder::new()
-                .build(&event_loop)
-                .unwrap(),
-        );
-        self.window = Some(window.clone());
+        event_loop.set_control_flow(ControlFlow::Poll);
+        event_loop.run_app(&mut self).unwrap();
+    }
+
+    /// Render one frame into an owned offscreen texture instead of a window
+    /// surface, returning tightly packed (no row padding) RGBA8 — for
+    /// screenshots, golden-image tests, or compositing a felt scene into a
+    /// host engine that owns its own swapchain. Reuses the same `renderers`
+    /// vec and `mount`/`mount_with` callback `run` would have called, just
+    /// swapping the surface for a texture; never touches winit, so this
+    /// doesn't need (and can't use) an event loop at all.
+    pub async fn render_to_image(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<u8>, crate::renderer::RendererError> {
+        let dev_id = self
+            .context
+            .device(None)
+            .await
+            .ok_or(crate::renderer::RendererError::NoDevice)?;
+        if self.renderers.len() <= dev_id {
+            self.renderers.resize_with(dev_id + 1, || None);
+        }
+        let device_handle = &self.context.devices[dev_id];
+
+        let vello_renderer = self.renderers[dev_id].get_or_insert_with(|| {
+            Renderer::new(&device_handle.device, vello::RendererOptions::default()).unwrap()
+        });
+
+        let mut scene = vello::Scene::new();
+
+        // Same lazily-created offscreen renderer `mount`/`mount_with`'s
+        // `LayerRenderHandle` uses, so a `CachedLayer` in the painted tree
+        // doesn't need its own special case here.
+        let layer_renderer_handle = self.layer_renderer.get_or_insert_with(|| {
+            Rc::new(RefCell::new(
+                Renderer::new(&device_handle.device, vello::RendererOptions::default()).unwrap(),
+            ))
+        });
+        let layer_handle = Some(LayerRenderHandle {
+            device: device_handle.device.clone(),
+            queue: device_handle.queue.clone(),
+            renderer: Rc::clone(layer_renderer_handle),
+        });
+
+        if let Some(callback) = &mut self.frame_callback {
+            // No window, no prior frame, no live input: every field here is
+            // a reasonable "nothing has happened yet" default.
+            let frame_ctx = FrameContext {
+                cursor_position: (0.0, 0.0),
+                pointer_buttons: PointerButtons::default(),
+                scroll_delta: (0.0, 0.0),
+                modifiers: Modifiers::default(),
+                elapsed: Duration::ZERO,
+            };
+            callback(&mut scene, width, height, layer_handle, &frame_ctx);
+        } else if let Some(callback) = &mut self.paint_callback {
+            callback(&mut scene, width, height, layer_handle);
+        } else {
+            scene.fill(
+                vello::peniko::Fill::NonZero,
+                vello::kurbo::Affine::IDENTITY,
+                &vello::peniko::Brush::Solid(vello::peniko::Color::BLACK),
+                None,
+                &vello::kurbo::Rect::new(0.0, 0.0, width as f64, height as f64),
+            );
+        }
+
+        crate::headless::render_scene_to_rgba8(
+            &device_handle.device,
+            &device_handle.queue,
+            vello_renderer,
+            &scene,
+            width,
+            height,
+            self.base_color,
+        )
+    }
+}
+
+impl ApplicationHandler for App {
+    /// Called both on first launch (no window yet) and when the app comes
+    /// back to the foreground after a [`Self::suspended`] (Android's
+    /// `onResume`, or the web canvas regaining visibility). The cached
+    /// window, if any, is reused verbatim — only the surface built against
+    /// it needs recreating.
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        let cached_window = match &mut self.render_state {
+            RenderState::Active { .. } => return,
+            RenderState::Suspended(window) => window.take(),
+        };
+
+        let window = cached_window.unwrap_or_else(|| {
+            Arc::new(
+                event_loop
+                    .create_window(Window::default_attributes())
+                    .unwrap(),
+            )
+        });
 
+        let size = window.inner_size();
         let surface = pollster::block_on(self.context.create_surface(
             window.clone(),
-            window.inner_size().width,
-            window.inner_size().height,
-            vello::wgpu::PresentMode::AutoVsync,
+            size.width,
+            size.height,
+            self.present_mode,
         ))
         .unwrap();
 
-        self.surface = Some(surface);
-        self.renderers.resize_with(1, || None);
-
-        event_loop
-            .run(move |event, elwt| {
-                elwt.set_control_flow(ControlFlow::Poll);
-
-                match event {
-                    Event::WindowEvent {
-                        event: WindowEvent::CloseRequested,
-                        ..
-                    } => elwt.exit(),
-                    Event::WindowEvent {
-                        event: WindowEvent::Resized(size),
-                        ..
-                    } => {
-                        if let Some(surface) = &mut self.surface {
-                            self.context
-                                .resize_surface(surface, size.width, size.height);
+        // Grow (never shrink or rebuild) `renderers` so a device index that
+        // already has a compiled `Renderer` from before the last suspend
+        // keeps it rather than recompiling its pipelines on every foreground.
+        if self.renderers.len() <= surface.dev_id {
+            self.renderers.resize_with(surface.dev_id + 1, || None);
+        }
+
+        self.render_state = RenderState::Active {
+            surface,
+            window: window.clone(),
+        };
+        window.request_redraw();
+    }
+
+    /// The native window may survive (Android) or not (most desktop
+    /// platforms never call this at all), but its GPU surface doesn't: drop
+    /// it and cache the window for `resumed` to hand back unchanged.
+    fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
+        if let RenderState::Active { window, .. } = &self.render_state {
+            self.render_state = RenderState::Suspended(Some(Arc::clone(window)));
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        let RenderState::Active { surface, window } = &mut self.render_state else {
+            return;
+        };
+
+        match event {
+            WindowEvent::CloseRequested => {
+                event_loop.exit();
+            }
+            WindowEvent::Resized(size) => {
+                self.context
+                    .resize_surface(surface, size.width, size.height);
+                window.request_redraw();
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor_position = (position.x, position.y);
+                if let Some(editor) = &self.editable_text {
+                    let local = vello::kurbo::Point::new(
+                        position.x - self.editable_text_origin.x,
+                        position.y - self.editable_text_origin.y,
+                    );
+                    editor.borrow_mut().extend_selection_to_point(local);
+                }
+                if let Some(callback) = &mut self.input_callback {
+                    callback(InputEvent::PointerMoved {
+                        x: position.x,
+                        y: position.y,
+                    });
+                }
+                window.request_redraw();
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let (x, y) = self.cursor_position;
+                let button = translate_mouse_button(button);
+                let pressed = state == ElementState::Pressed;
+                self.pointer_buttons.set(button, pressed);
+                if button == PointerButton::Left {
+                    if let Some(editor) = &self.editable_text {
+                        let local = vello::kurbo::Point::new(
+                            x - self.editable_text_origin.x,
+                            y - self.editable_text_origin.y,
+                        );
+                        let mut editor = editor.borrow_mut();
+                        if pressed {
+                            editor.set_caret_from_point(local);
+                        } else {
+                            editor.end_drag();
                         }
-                        self.window.as_ref().unwrap().request_redraw();
                     }
-                    Event::WindowEvent {
-                        event: WindowEvent::RedrawRequested,
-                        ..
-                    } => {
-                        if let (Some(surface), Some(window)) = (&mut self.surface, &self.window) {
-                            let width = surface.config.width;
-                            let height = surface.config.height;
-                            let device_handle = &self.context.devices[surface.dev_id];
-
-                            let surface_texture = surface.surface.get_current_texture().unwrap();
-
-                            let renderer =
-                                self.renderers[surface.dev_id].get_or_insert_with(|| {
-                                    Renderer::new(
-                                        &device_handle.device,
-                                        vello::RendererOptions {
-                                            surface_format: Some(surface.format),
-                                            use_cpu: false,
-                                            antialiasing_support: vello::AaSupport::all(),
-                                            num_init_threads: None,
-                                        },
-                                    )
-                                    .unwrap()
-                                });
-
-                            let mut scene = vello::Scene::new();
-
-                            // Call the paint callback
-                            if let Some(callback) = &mut self.paint_callback {
-                                callback(&mut scene, width, height);
-                            } else {
-                                // Default clear
-                                scene.fill(
-                                    vello::peniko::Fill::NonZero,
-                                    vello::kurbo::Affine::IDENTITY,
-                                    &vello::peniko::Brush::Solid(vello::peniko::Color::BLACK),
-                                    None,
-                                    &vello::kurbo::Rect::new(0.0, 0.0, width as f64, height as f64),
-                                );
+                }
+                if let Some(callback) = &mut self.input_callback {
+                    let input_event = match state {
+                        ElementState::Pressed => InputEvent::PointerDown { x, y, button },
+                        ElementState::Released => InputEvent::PointerUp { x, y, button },
+                    };
+                    callback(input_event);
+                }
+                window.request_redraw();
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let (x, y) = self.cursor_position;
+                let (delta_x, delta_y) = match delta {
+                    MouseScrollDelta::LineDelta(dx, dy) => (dx as f64 * 20.0, dy as f64 * 20.0),
+                    MouseScrollDelta::PixelDelta(pos) => (pos.x, pos.y),
+                };
+                self.scroll_accum.0 += delta_x;
+                self.scroll_accum.1 += delta_y;
+                if let Some(callback) = &mut self.input_callback {
+                    callback(InputEvent::Wheel {
+                        x,
+                        y,
+                        delta_x,
+                        delta_y,
+                    });
+                }
+                window.request_redraw();
+            }
+            WindowEvent::KeyboardInput { event, .. } => {
+                let PhysicalKey::Code(code) = event.physical_key else {
+                    return;
+                };
+                let pressed = event.state == ElementState::Pressed;
+                if !event.repeat && self.modifiers.update(code, pressed) {
+                    window.request_redraw();
+                    return;
+                }
+                if !pressed {
+                    return;
+                }
+                let Some(editor) = self.editable_text.clone() else {
+                    return;
+                };
+                let mut editor = editor.borrow_mut();
+                let handled = match code {
+                    KeyCode::ArrowLeft => {
+                        editor.move_caret(CaretMovement::Left, self.modifiers.shift);
+                        true
+                    }
+                    KeyCode::ArrowRight => {
+                        editor.move_caret(CaretMovement::Right, self.modifiers.shift);
+                        true
+                    }
+                    KeyCode::Home => {
+                        editor.move_caret(CaretMovement::Home, self.modifiers.shift);
+                        true
+                    }
+                    KeyCode::End => {
+                        editor.move_caret(CaretMovement::End, self.modifiers.shift);
+                        true
+                    }
+                    KeyCode::Backspace => {
+                        editor.backspace();
+                        true
+                    }
+                    // Leave anything chorded with control/meta to whatever
+                    // shortcut it's meant for, rather than inserting it as text.
+                    _ if self.modifiers.control || self.modifiers.meta => false,
+                    _ => match &event.text {
+                        Some(text) if text.chars().any(|c| !c.is_control()) => {
+                            for ch in text.chars().filter(|c| !c.is_control()) {
+                                editor.insert_char(ch);
                             }
-
-                            renderer
-                                .render_to_surface(
-                                    &device_handle.device,
-                                    &device_handle.queue,
-                                    &scene,
-                                    &surface_texture,
-                                    &vello::RenderParams {
-                                        base_color: vello::peniko::Color::BLACK,
-                                        width,
-                                        height,
-                                        antialiasing_method: vello::AaConfig::Area,
-                                    },
-                                )
-                                .unwrap();
-
-                            surface_texture.present();
-
-                            // Request next frame for animation
-                            window.request_redraw();
+                            true
                         }
-                    }
-                    _ => {}
+                        _ => false,
+                    },
+                };
+                drop(editor);
+                if handled {
+                    window.request_redraw();
                 }
-            })
-            .unwrap();
+            }
+            WindowEvent::RedrawRequested => {
+                let now = Instant::now();
+                let elapsed = self
+                    .last_frame_instant
+                    .map_or(Duration::ZERO, |last| now.duration_since(last));
+                self.last_frame_instant = Some(now);
+                let frame_ctx = FrameContext {
+                    cursor_position: self.cursor_position,
+                    pointer_buttons: self.pointer_buttons,
+                    scroll_delta: std::mem::take(&mut self.scroll_accum),
+                    modifiers: self.modifiers,
+                    elapsed,
+                };
+
+                let width = surface.config.width;
+                let height = surface.config.height;
+                let device_handle = &self.context.devices[surface.dev_id];
+
+                let surface_texture = surface.surface.get_current_texture().unwrap();
+
+                let renderer = self.renderers[surface.dev_id].get_or_insert_with(|| {
+                    Renderer::new(
+                        &device_handle.device,
+                        vello::RendererOptions {
+                            surface_format: Some(surface.format),
+                            use_cpu: false,
+                            antialiasing_support: vello::AaSupport::all(),
+                            num_init_threads: None,
+                        },
+                    )
+                    .unwrap()
+                });
+
+                let mut scene = vello::Scene::new();
+
+                // `self.layer_renderer` is a field disjoint from `surface`/
+                // `device_handle` above, so lazily creating it here doesn't
+                // conflict with their borrows — the same way mutating
+                // `self.renderers` above doesn't either.
+                let layer_renderer_handle = self.layer_renderer.get_or_insert_with(|| {
+                    Rc::new(RefCell::new(
+                        Renderer::new(&device_handle.device, vello::RendererOptions::default())
+                            .unwrap(),
+                    ))
+                });
+                let layer_handle = Some(LayerRenderHandle {
+                    device: device_handle.device.clone(),
+                    queue: device_handle.queue.clone(),
+                    renderer: Rc::clone(layer_renderer_handle),
+                });
+
+                // Prefer `mount_with`'s frame callback (it gets the aggregated
+                // input/timing state too) over plain `mount`'s; no registered
+                // callback at all means there's nothing to compare
+                // frame-to-frame, so the default clear always counts as changed.
+                let mut needs_redraw = if let Some(callback) = &mut self.frame_callback {
+                    callback(&mut scene, width, height, layer_handle, &frame_ctx)
+                } else if let Some(callback) = &mut self.paint_callback {
+                    callback(&mut scene, width, height, layer_handle)
+                } else {
+                    // Default clear
+                    scene.fill(
+                        vello::peniko::Fill::NonZero,
+                        vello::kurbo::Affine::IDENTITY,
+                        &vello::peniko::Brush::Solid(vello::peniko::Color::BLACK),
+                        None,
+                        &vello::kurbo::Rect::new(0.0, 0.0, width as f64, height as f64),
+                    );
+                    true
+                };
+
+                if let Some(editor) = self.editable_text.clone() {
+                    let text_renderer = self
+                        .text_renderer
+                        .get_or_insert_with(|| simple_text::SimpleText::new(&device_handle.device));
+                    let transform =
+                        vello::kurbo::Affine::translate(self.editable_text_origin.to_vec2());
+                    editor.borrow_mut().draw(
+                        text_renderer,
+                        &mut scene,
+                        transform,
+                        window.scale_factor(),
+                        &device_handle.queue,
+                        &vello::peniko::Brush::Solid(vello::peniko::Color::rgba8(70, 130, 230, 90)),
+                        &vello::peniko::Brush::Solid(vello::peniko::Color::rgb8(20, 20, 20)),
+                    );
+                    // A mounted editor can change from input alone (a blinking
+                    // caret, a fresh keystroke) independent of whatever the
+                    // paint/frame callback itself returned.
+                    needs_redraw = true;
+                }
+
+                if needs_redraw {
+                    renderer
+                        .render_to_surface(
+                            &device_handle.device,
+                            &device_handle.queue,
+                            &scene,
+                            &surface_texture,
+                            &vello::RenderParams {
+                                base_color: self.base_color,
+                                width,
+                                height,
+                                antialiasing_method: self.antialiasing,
+                            },
+                        )
+                        .unwrap();
+
+                    surface_texture.present();
+
+                    // Request next frame for animation
+                    window.request_redraw();
+                } else {
+                    // Nothing changed: let the acquired texture go without
+                    // presenting it, and don't schedule another frame — the
+                    // GPU stays idle until an input event or resize requests
+                    // one.
+                    drop(surface_texture);
+                }
+            }
+            _ => {}
+        }
     }
 }
@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 use vello::util::{RenderContext, RenderSurface};
@@ -5,7 +6,207 @@ use winit::dpi::{LogicalSize, PhysicalSize};
 use winit::window::Window;
 
 use crate::simple_text::SimpleText;
-use crate::stats::{Sample, Stats};
+use crate::stats::{BufferUtilization, Sample, Stats};
+
+/// Number of in-flight GPU timestamp queries kept around at once. Readback
+/// via `map_async` lags the submission that produced it by a frame or two,
+/// so we keep a small ring instead of blocking on the map every frame.
+const GPU_QUERY_RING_SIZE: usize = 3;
+
+/// One frame's worth of GPU timestamp query state: a 2-entry query set
+/// (render-pass begin/end), the buffer it resolves into, and a mappable
+/// buffer the CPU reads the resolved timestamps back from.
+struct GpuTimestampQuery {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Set once a frame has written into this slot's query set and not yet
+    /// been read back.
+    pending: bool,
+    /// Flipped to `true` by the `map_async` callback once `readback_buffer`
+    /// is safe to read, since wgpu has no synchronous "is mapped" query.
+    mapped: Arc<AtomicBool>,
+}
+
+impl GpuTimestampQuery {
+    fn new(device: &wgpu::Device) -> Self {
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("felt-platform GPU timing"),
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("felt-platform GPU timing resolve"),
+            size: 2 * std::mem::size_of::<u64>() as u64,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("felt-platform GPU timing readback"),
+            size: 2 * std::mem::size_of::<u64>() as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            pending: false,
+            mapped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+/// Write the begin-of-render timestamp into `queries[frame_index % len]`,
+/// skipping the slot if its previous readback hasn't landed yet. Returns the
+/// slot index so the caller can close it out with `end_gpu_timestamp`.
+fn begin_gpu_timestamp(
+    queries: &mut [GpuTimestampQuery],
+    frame_index: usize,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+) -> Option<usize> {
+    let slot = frame_index % queries.len();
+    let query = &mut queries[slot];
+    if query.pending {
+        return None;
+    }
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("felt-platform GPU timing begin"),
+    });
+    encoder.write_timestamp(&query.query_set, 0);
+    queue.submit([encoder.finish()]);
+    Some(slot)
+}
+
+/// Write the end-of-render timestamp, resolve both queries into a mappable
+/// buffer, and kick off an async `map_async` that `poll_gpu_timestamps` will
+/// later harvest.
+fn end_gpu_timestamp(
+    queries: &mut [GpuTimestampQuery],
+    slot: usize,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+) {
+    let query = &mut queries[slot];
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("felt-platform GPU timing end"),
+    });
+    encoder.write_timestamp(&query.query_set, 1);
+    encoder.resolve_query_set(&query.query_set, 0..2, &query.resolve_buffer, 0);
+    encoder.copy_buffer_to_buffer(
+        &query.resolve_buffer,
+        0,
+        &query.readback_buffer,
+        0,
+        query.resolve_buffer.size(),
+    );
+    queue.submit([encoder.finish()]);
+    query.pending = true;
+
+    let mapped = Arc::clone(&query.mapped);
+    query
+        .readback_buffer
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, move |result| {
+            if result.is_ok() {
+                mapped.store(true, Ordering::Release);
+            }
+        });
+}
+
+/// Vello's `render_to_texture` does not currently expose the bump allocators
+/// it uses internally for binning/ptcl/segment staging, so there's no real
+/// occupancy to report yet — this crate has no way to warn "about to
+/// overflow a staging buffer" until Vello's public API surfaces that state.
+/// This stays as a seam `render()` already calls into, ready to return real
+/// figures once it does; until then it's a documented gap, not a delivered
+/// part of the GPU-timing work alongside it (see `Snapshot::draw_layer`,
+/// which shows an explicit "not available" row rather than hiding the gap).
+fn sample_buffer_utilization() -> Vec<BufferUtilization> {
+    Vec::new()
+}
+
+/// Poll for any in-flight timestamp readbacks that have landed, converting
+/// each to a GPU frame time in microseconds and feeding it into `stats`.
+fn poll_gpu_timestamps(
+    queries: &mut [GpuTimestampQuery],
+    device: &wgpu::Device,
+    timestamp_period_ns: f32,
+    stats: &mut Stats,
+) {
+    device.poll(wgpu::Maintain::Poll);
+    for query in queries.iter_mut() {
+        if !query.pending || !query.mapped.load(Ordering::Acquire) {
+            continue;
+        }
+
+        {
+            let view = query.readback_buffer.slice(..).get_mapped_range();
+            let start = u64::from_le_bytes(view[0..8].try_into().unwrap());
+            let end = u64::from_le_bytes(view[8..16].try_into().unwrap());
+            let elapsed_ns = end.saturating_sub(start) as f64 * timestamp_period_ns as f64;
+            stats.add_gpu_sample((elapsed_ns * 0.001) as u64);
+        }
+        query.readback_buffer.unmap();
+        query.mapped.store(false, Ordering::Release);
+        query.pending = false;
+    }
+}
+
+fn present_mode_for(vsync: VSync) -> wgpu::PresentMode {
+    match vsync {
+        VSync::Off => wgpu::PresentMode::Immediate,
+        VSync::On => wgpu::PresentMode::Fifo,
+        VSync::Mailbox => wgpu::PresentMode::Mailbox,
+    }
+}
+
+/// Create and configure a `RenderSurface` against `window`, shared by
+/// `Renderer::new` and `Renderer::resume` since both need the same
+/// window-size validation and alpha mode selection. Returns
+/// `RendererError::NoWindow` if the native window has no valid size yet
+/// (e.g. polled too early in an Android resume, before the system has
+/// handed back a usable native window).
+async fn create_surface(
+    context: &mut RenderContext,
+    window: &Arc<Window>,
+    present_mode: wgpu::PresentMode,
+) -> Result<RenderSurface<'static>, RendererError> {
+    let size = window.inner_size();
+    if size.width == 0 || size.height == 0 {
+        return Err(RendererError::NoWindow);
+    }
+
+    let wgpu_surface = context.instance.create_surface(Arc::clone(window))?;
+    let mut surface = context
+        .create_render_surface(wgpu_surface, size.width, size.height, present_mode)
+        .await?;
+
+    // Override alpha mode to support transparency
+    let dev_id = surface.dev_id;
+    let caps = surface
+        .surface
+        .get_capabilities(context.devices[dev_id].adapter());
+    let alpha_mode = caps
+        .alpha_modes
+        .iter()
+        .find(|&&mode| mode == wgpu::CompositeAlphaMode::PreMultiplied)
+        .or_else(|| {
+            caps.alpha_modes
+                .iter()
+                .find(|&&mode| mode == wgpu::CompositeAlphaMode::PostMultiplied)
+        })
+        .copied()
+        .unwrap_or(caps.alpha_modes[0]);
+
+    surface.config.alpha_mode = alpha_mode;
+    surface
+        .surface
+        .configure(&context.devices[dev_id].device, &surface.config);
+
+    Ok(surface)
+}
 
 /// Controls vertical synchronization and frame presentation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,61 +254,48 @@ pub struct Renderer {
     vello_renderer: Option<vello::Renderer>,
     scale_factor: f64,
     stats: Stats,
-    simple_text: SimpleText,
+    /// Device-dependent, like `surface`/`vello_renderer`: torn down by
+    /// `suspend()` and rebuilt by `resume()` since its glyph atlas texture
+    /// belongs to whichever device was current when it was created.
+    simple_text: Option<SimpleText>,
     show_stats: bool,
     vsync: VSync,
+    aa_config: vello::AaConfig,
     last_frame_start: Option<Instant>,
+    pending_scroll_delta: (f64, f64),
+    /// Committed total of every `pending_scroll_delta` drained by `render()`
+    /// so far — pans `build_test_scene`'s content, the one thing in this
+    /// stack that actually owns the pointer right now.
+    scroll_offset: (f64, f64),
+    gpu_timestamp_queries: Option<Vec<GpuTimestampQuery>>,
+    timestamp_period_ns: f32,
+    gpu_frame_index: usize,
 }
 
 impl Renderer {
     pub async fn new(window: Arc<Window>, options: RendererOptions) -> Result<Self, RendererError> {
         let mut context = RenderContext::new();
-
-        let size = window.inner_size();
         let scale_factor = window.scale_factor();
+        let present_mode = present_mode_for(options.vsync);
 
-        let wgpu_surface = context.instance.create_surface(Arc::clone(&window))?;
-
-        let present_mode = match options.vsync {
-            VSync::Off => wgpu::PresentMode::Immediate,
-            VSync::On => wgpu::PresentMode::Fifo,
-            VSync::Mailbox => wgpu::PresentMode::Mailbox,
-        };
-
-        let mut surface = context
-            .create_render_surface(
-                wgpu_surface,
-                size.width,
-                size.height,
-                present_mode,
-            )
-            .await?;
-
-        // Override alpha mode to support transparency
+        let surface = create_surface(&mut context, &window, present_mode).await?;
         let dev_id = surface.dev_id;
-        let caps = surface
-            .surface
-            .get_capabilities(context.devices[dev_id].adapter());
-        let alpha_mode = caps
-            .alpha_modes
-            .iter()
-            .find(|&&mode| mode == wgpu::CompositeAlphaMode::PreMultiplied)
-            .or_else(|| {
-                caps.alpha_modes
-                    .iter()
-                    .find(|&&mode| mode == wgpu::CompositeAlphaMode::PostMultiplied)
-            })
-            .copied()
-            .unwrap_or(caps.alpha_modes[0]);
+        let device_handle = &context.devices[dev_id];
 
-        surface.config.alpha_mode = alpha_mode;
-        surface
-            .surface
-            .configure(&context.devices[dev_id].device, &surface.config);
-
-        let device_handle = &context.devices[surface.dev_id];
         let vello_renderer =
             vello::Renderer::new(&device_handle.device, vello::RendererOptions::default())?;
+        let simple_text = SimpleText::new(&device_handle.device);
+
+        let gpu_timestamp_queries = device_handle
+            .device
+            .features()
+            .contains(wgpu::Features::TIMESTAMP_QUERY)
+            .then(|| {
+                (0..GPU_QUERY_RING_SIZE)
+                    .map(|_| GpuTimestampQuery::new(&device_handle.device))
+                    .collect()
+            });
+        let timestamp_period_ns = device_handle.queue.get_timestamp_period();
 
         Ok(Self {
             context,
@@ -115,13 +303,67 @@ impl Renderer {
             vello_renderer: Some(vello_renderer),
             scale_factor,
             stats: Stats::new(),
-            simple_text: SimpleText::new(),
+            simple_text: Some(simple_text),
             show_stats: options.show_stats,
             vsync: options.vsync,
+            aa_config: vello::AaConfig::Msaa16,
             last_frame_start: None,
+            pending_scroll_delta: (0.0, 0.0),
+            scroll_offset: (0.0, 0.0),
+            gpu_timestamp_queries,
+            timestamp_period_ns,
+            gpu_frame_index: 0,
         })
     }
 
+    /// Tear down the GPU surface and every device-dependent resource built
+    /// against it, ahead of the native window handle becoming invalid (e.g.
+    /// Android's `onPause`). `render()`/`render_empty()`/`render_benchmark()`
+    /// no-op while suspended rather than erroring; call `resume()` with the
+    /// new window once one is available again.
+    pub fn suspend(&mut self) {
+        self.surface = None;
+        self.vello_renderer = None;
+        self.simple_text = None;
+        self.gpu_timestamp_queries = None;
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.surface.is_none()
+    }
+
+    /// Recreate the surface (and every device-dependent resource alongside
+    /// it, since `resume` may land on a different device than before
+    /// `suspend`) against `window`, reapplying the current vsync mode and
+    /// alpha mode selection.
+    pub async fn resume(&mut self, window: Arc<Window>) -> Result<(), RendererError> {
+        self.scale_factor = window.scale_factor();
+        let present_mode = present_mode_for(self.vsync);
+
+        let surface = create_surface(&mut self.context, &window, present_mode).await?;
+        let dev_id = surface.dev_id;
+        let device_handle = &self.context.devices[dev_id];
+
+        self.vello_renderer = Some(vello::Renderer::new(
+            &device_handle.device,
+            vello::RendererOptions::default(),
+        )?);
+        self.simple_text = Some(SimpleText::new(&device_handle.device));
+        self.gpu_timestamp_queries = device_handle
+            .device
+            .features()
+            .contains(wgpu::Features::TIMESTAMP_QUERY)
+            .then(|| {
+                (0..GPU_QUERY_RING_SIZE)
+                    .map(|_| GpuTimestampQuery::new(&device_handle.device))
+                    .collect()
+            });
+        self.timestamp_period_ns = device_handle.queue.get_timestamp_period();
+        self.surface = Some(surface);
+
+        Ok(())
+    }
+
     pub fn toggle_stats(&mut self) {
         self.show_stats = !self.show_stats;
     }
@@ -134,10 +376,43 @@ impl Renderer {
         self.show_stats
     }
 
+    /// Reset the tracked min/max frame times shown in the stats overlay,
+    /// e.g. after an intentional one-off stall (window move, shader warmup)
+    /// that shouldn't keep skewing the displayed range.
+    pub fn clear_min_and_max(&mut self) {
+        self.stats.clear_min_and_max();
+    }
+
     pub fn vsync(&self) -> VSync {
         self.vsync
     }
 
+    /// Cycle vsync Off -> On -> Mailbox -> Off, reconfiguring the surface
+    /// present mode live. A DevTools-style toggle for comparing latency vs.
+    /// tearing without restarting the app.
+    pub fn cycle_vsync(&mut self) {
+        let next = match self.vsync {
+            VSync::Off => VSync::On,
+            VSync::On => VSync::Mailbox,
+            VSync::Mailbox => VSync::Off,
+        };
+        self.set_vsync(next);
+    }
+
+    pub fn aa_config(&self) -> vello::AaConfig {
+        self.aa_config
+    }
+
+    /// Cycle the antialiasing method Area -> 8xMSAA -> 16xMSAA -> Area, so
+    /// the method shown in the stats overlay actually drives rendering.
+    pub fn cycle_aa_config(&mut self) {
+        self.aa_config = match self.aa_config {
+            vello::AaConfig::Area => vello::AaConfig::Msaa8,
+            vello::AaConfig::Msaa8 => vello::AaConfig::Msaa16,
+            vello::AaConfig::Msaa16 => vello::AaConfig::Area,
+        };
+    }
+
     /// Change vsync mode at runtime by reconfiguring the surface.
     /// Common use case: set to Immediate during resize for lowest latency,
     /// then restore to On/Mailbox when resize completes.
@@ -171,7 +446,31 @@ impl Renderer {
         self.scale_factor = scale_factor;
     }
 
+    /// Accumulate a wheel/trackpad scroll delta (in logical pixels) observed
+    /// since the last call to `take_scroll_delta`. `render()` drains this
+    /// once per frame and adds it to `scroll_offset`, panning
+    /// `build_test_scene`'s content — this stack has no widget tree to
+    /// dispatch into, so the built-in test scene is the thing that owns the
+    /// pointer.
+    pub fn accumulate_scroll(&mut self, delta: (f64, f64)) {
+        self.pending_scroll_delta.0 += delta.0;
+        self.pending_scroll_delta.1 += delta.1;
+    }
+
+    /// Drain and reset the accumulated scroll delta.
+    pub fn take_scroll_delta(&mut self) -> (f64, f64) {
+        std::mem::take(&mut self.pending_scroll_delta)
+    }
+
     pub fn render(&mut self) -> Result<(), RendererError> {
+        if self.is_suspended() {
+            return Ok(());
+        }
+
+        let delta = self.take_scroll_delta();
+        self.scroll_offset.0 += delta.0;
+        self.scroll_offset.1 += delta.1;
+
         if self.show_stats {
             let frame_start = Instant::now();
             if let Some(last_start) = self.last_frame_start {
@@ -181,6 +480,20 @@ impl Renderer {
                 });
             }
             self.last_frame_start = Some(frame_start);
+
+            let dev_id = self
+                .surface
+                .as_ref()
+                .ok_or(RendererError::NoSurface)?
+                .dev_id;
+            if let Some(queries) = &mut self.gpu_timestamp_queries {
+                poll_gpu_timestamps(
+                    queries,
+                    &self.context.devices[dev_id].device,
+                    self.timestamp_period_ns,
+                    &mut self.stats,
+                );
+            }
         }
 
         let dev_id = self
@@ -193,15 +506,20 @@ impl Renderer {
         let mut scene = vello::Scene::new();
         self.build_test_scene(&mut scene);
 
-        if self.show_stats {
+        if self.show_stats
+            && let Some(simple_text) = self.simple_text.as_mut()
+        {
             let snapshot = self.stats.snapshot();
             snapshot.draw_layer(
                 &mut scene,
-                &mut self.simple_text,
+                simple_text,
                 (size.width as f64, size.height as f64),
                 self.stats.samples(),
+                self.stats.gpu_samples(),
                 self.vsync,
-                vello::AaConfig::Msaa16,
+                self.aa_config,
+                self.scale_factor,
+                &self.context.devices[dev_id].queue,
             );
         }
 
@@ -218,7 +536,20 @@ impl Renderer {
             base_color: vello::peniko::Color::TRANSPARENT,
             width: size.width,
             height: size.height,
-            antialiasing_method: vello::AaConfig::Msaa16,
+            antialiasing_method: self.aa_config,
+        };
+
+        let gpu_query_slot = if self.show_stats {
+            self.gpu_timestamp_queries.as_mut().and_then(|queries| {
+                begin_gpu_timestamp(
+                    queries,
+                    self.gpu_frame_index,
+                    &device_handle.device,
+                    &device_handle.queue,
+                )
+            })
+        } else {
+            None
         };
 
         renderer.render_to_texture(
@@ -229,6 +560,19 @@ impl Renderer {
             &render_params,
         )?;
 
+        if let Some(slot) = gpu_query_slot
+            && let Some(queries) = &mut self.gpu_timestamp_queries
+        {
+            end_gpu_timestamp(queries, slot, &device_handle.device, &device_handle.queue);
+            self.gpu_frame_index = self.gpu_frame_index.wrapping_add(1);
+        }
+
+        if self.show_stats {
+            self.stats.set_buffer_utilization(sample_buffer_utilization());
+        }
+
+        let surface = self.surface.as_mut().unwrap();
+        let device_handle = &self.context.devices[dev_id];
         let mut encoder =
             device_handle
                 .device
@@ -251,15 +595,22 @@ impl Renderer {
         Ok(())
     }
 
-    pub fn render_empty(&mut self) -> Result<(), RendererError> {
+    /// Render `scene` into the live surface's offscreen target texture and
+    /// blit/present it, the tail shared verbatim by [`Self::render_empty`]
+    /// and [`Self::render_benchmark`]. [`Self::render`] doesn't use this: it
+    /// needs to close out a GPU timestamp query in the gap between
+    /// `render_to_texture` and the blit, so it keeps its own copy of this
+    /// sequence with that instrumentation threaded through.
+    fn render_scene_to_surface(
+        &mut self,
+        scene: &vello::Scene,
+        render_params: &vello::RenderParams,
+    ) -> Result<(), RendererError> {
         let dev_id = self
             .surface
             .as_ref()
             .ok_or(RendererError::NoSurface)?
             .dev_id;
-        let size = self.physical_size();
-
-        let scene = vello::Scene::new();
 
         let surface = self.surface.as_mut().unwrap();
         let renderer = self
@@ -270,19 +621,12 @@ impl Renderer {
 
         let surface_texture = surface.surface.get_current_texture()?;
 
-        let render_params = vello::RenderParams {
-            base_color: vello::peniko::Color::TRANSPARENT,
-            width: size.width,
-            height: size.height,
-            antialiasing_method: vello::AaConfig::Msaa16,
-        };
-
         renderer.render_to_texture(
             &device_handle.device,
             &device_handle.queue,
-            &scene,
+            scene,
             &surface.target_view,
-            &render_params,
+            render_params,
         )?;
 
         let mut encoder =
@@ -307,26 +651,13 @@ impl Renderer {
         Ok(())
     }
 
-    pub fn render_benchmark(&mut self, rect_count: usize) -> Result<(), RendererError> {
-        let dev_id = self
-            .surface
-            .as_ref()
-            .ok_or(RendererError::NoSurface)?
-            .dev_id;
-        let size = self.physical_size();
-
-        let mut scene = vello::Scene::new();
-        self.build_benchmark_scene(&mut scene, rect_count);
-
-        let surface = self.surface.as_mut().unwrap();
-        let renderer = self
-            .vello_renderer
-            .as_mut()
-            .ok_or(RendererError::NoRenderer)?;
-        let device_handle = &self.context.devices[dev_id];
-
-        let surface_texture = surface.surface.get_current_texture()?;
+    pub fn render_empty(&mut self) -> Result<(), RendererError> {
+        if self.is_suspended() {
+            return Ok(());
+        }
 
+        let size = self.physical_size();
+        let scene = vello::Scene::new();
         let render_params = vello::RenderParams {
             base_color: vello::peniko::Color::TRANSPARENT,
             width: size.width,
@@ -334,34 +665,25 @@ impl Renderer {
             antialiasing_method: vello::AaConfig::Msaa16,
         };
 
-        renderer.render_to_texture(
-            &device_handle.device,
-            &device_handle.queue,
-            &scene,
-            &surface.target_view,
-            &render_params,
-        )?;
-
-        let mut encoder =
-            device_handle
-                .device
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("Surface Blit"),
-                });
+        self.render_scene_to_surface(&scene, &render_params)
+    }
 
-        surface.blitter.copy(
-            &device_handle.device,
-            &mut encoder,
-            &surface.target_view,
-            &surface_texture
-                .texture
-                .create_view(&wgpu::TextureViewDescriptor::default()),
-        );
+    pub fn render_benchmark(&mut self, rect_count: usize) -> Result<(), RendererError> {
+        if self.is_suspended() {
+            return Ok(());
+        }
 
-        device_handle.queue.submit([encoder.finish()]);
-        surface_texture.present();
+        let size = self.physical_size();
+        let mut scene = vello::Scene::new();
+        self.build_benchmark_scene(&mut scene, rect_count);
+        let render_params = vello::RenderParams {
+            base_color: vello::peniko::Color::TRANSPARENT,
+            width: size.width,
+            height: size.height,
+            antialiasing_method: vello::AaConfig::Msaa16,
+        };
 
-        Ok(())
+        self.render_scene_to_surface(&scene, &render_params)
     }
 
     fn build_test_scene(&self, scene: &mut vello::Scene) {
@@ -374,11 +696,14 @@ impl Renderer {
 
         let width = surface.config.width as f64;
         let height = surface.config.height as f64;
+        // Wheel/trackpad scroll pans the whole test scene, so
+        // `accumulate_scroll`/`take_scroll_delta` has a visible effect.
+        let pan = Affine::translate(self.scroll_offset);
 
         // Draw a red rectangle
         scene.fill(
             vello::peniko::Fill::NonZero,
-            Affine::IDENTITY,
+            pan,
             Color::from_rgb8(255, 0, 0),
             None,
             &Rect::new(50.0, 50.0, 250.0, 150.0),
@@ -387,7 +712,7 @@ impl Renderer {
         // Draw a green rectangle
         scene.fill(
             vello::peniko::Fill::NonZero,
-            Affine::IDENTITY,
+            pan,
             Color::from_rgb8(0, 255, 0),
             None,
             &Rect::new(width - 250.0, 50.0, width - 50.0, 150.0),
@@ -398,7 +723,7 @@ impl Renderer {
         let center_y = height / 2.0;
         scene.fill(
             vello::peniko::Fill::NonZero,
-            Affine::IDENTITY,
+            pan,
             Color::from_rgb8(0, 0, 255),
             None,
             &Rect::new(
@@ -502,6 +827,15 @@ pub enum RendererError {
     #[error("No surface available")]
     NoSurface,
 
+    #[error("No valid native window to render into")]
+    NoWindow,
+
     #[error("No renderer available")]
     NoRenderer,
+
+    #[error("No compatible GPU adapter/device available")]
+    NoDevice,
+
+    #[error("Failed to map a readback buffer")]
+    MapFailed,
 }
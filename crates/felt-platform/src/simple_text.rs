@@ -1,11 +1,20 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 use std::sync::{Arc, OnceLock};
-use skrifa::{MetadataProvider, raw::{FileRef, FontRef}};
-use vello::kurbo::Affine;
+use parley::{Alignment, FontContext, Layout, LayoutContext, PositionedLayoutItem, StyleProperty};
+use skrifa::{GlyphId, MetadataProvider, raw::{FileRef, FontRef}};
+use vello::kurbo::{Affine, Rect};
 use vello::peniko::{Blob, Brush, Fill, FontData, color::palette};
 use vello::{Glyph, Scene};
 
+use crate::glyph_atlas::{GlyphAtlas, GlyphCacheStats, snap_transform_to_pixel_grid};
+
 const ROBOTO_FONT: &[u8] = include_bytes!("../assets/Roboto-Regular.ttf");
 
+/// Side length, in pixels, of [`SimpleText`]'s shared glyph atlas texture.
+const GLYPH_ATLAS_SIZE: u32 = 1024;
+
 static ROBOTO_FONT_DATA: OnceLock<FontData> = OnceLock::new();
 
 fn get_roboto_font() -> &'static FontData {
@@ -14,13 +23,239 @@ fn get_roboto_font() -> &'static FontData {
     })
 }
 
-pub struct SimpleText;
+/// A variable-font axis setting, e.g. `("wght", 600.0)` or `("wdth", 87.5)`.
+pub type AxisValue = (&'static str, f32);
 
-impl SimpleText {
+/// A registry of fonts grouped by family name, with an ordered fallback
+/// chain used when the requested family is missing a glyph.
+///
+/// Unlike [`SimpleText`]'s parley-backed paragraph layout, this operates at
+/// the raw skrifa level so callers can pin exact variable-font axes and
+/// control fallback per glyph rather than per run.
+pub struct FontRegistry {
+    families: HashMap<String, Vec<FontData>>,
+    fallback_order: Vec<String>,
+}
+
+impl FontRegistry {
     pub fn new() -> Self {
-        Self
+        let mut registry = Self {
+            families: HashMap::new(),
+            fallback_order: Vec::new(),
+        };
+        registry.register_bytes("Roboto", ROBOTO_FONT.to_vec());
+        registry
+    }
+
+    /// Register a font's raw bytes under `family`, appending it to that
+    /// family's list (later entries are tried if earlier ones lack a glyph).
+    pub fn register_bytes(&mut self, family: &str, data: Vec<u8>) -> FontData {
+        let font = FontData::new(Blob::new(Arc::new(data)), 0);
+        self.families
+            .entry(family.to_string())
+            .or_default()
+            .push(font.clone());
+        font
+    }
+
+    /// Register a font loaded from disk under `family`. See [`Self::register_bytes`].
+    pub fn register_file(&mut self, family: &str, path: impl AsRef<Path>) -> std::io::Result<FontData> {
+        let data = fs::read(path)?;
+        Ok(self.register_bytes(family, data))
+    }
+
+    /// Set the ordered list of families to fall back to when `family` is
+    /// missing a requested glyph, e.g. `["Noto Sans CJK", "Noto Color Emoji"]`.
+    pub fn set_fallback_order(&mut self, families: impl IntoIterator<Item = String>) {
+        self.fallback_order = families.into_iter().collect();
+    }
+
+    /// Resolve `ch` to a glyph id, trying `family` first and then each
+    /// fallback family in order. Returns the font that actually supplied the
+    /// glyph so the caller can draw with the matching font/axes.
+    pub fn resolve_glyph(&self, family: &str, ch: char) -> Option<(&FontData, GlyphId)> {
+        std::iter::once(family)
+            .chain(self.fallback_order.iter().map(String::as_str))
+            .find_map(|name| {
+                self.families.get(name)?.iter().find_map(|font| {
+                    let font_ref = to_font_ref(font)?;
+                    let gid = font_ref.charmap().map(ch).unwrap_or_default();
+                    (gid.to_u32() != 0).then_some((font, gid))
+                })
+            })
+    }
+}
+
+impl Default for FontRegistry {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
+/// Build the normalized variable-font coordinates for `axes` (e.g.
+/// `[("wght", 600.0)]`), for feeding into `draw_glyphs().normalized_coords(..)`.
+pub fn normalized_coords(font: &FontData, axes: &[AxisValue]) -> Vec<skrifa::instance::NormalizedCoord> {
+    let font_ref = to_font_ref(font).expect("invalid font data");
+    let location = font_ref.axes().location(axes.iter().copied());
+    location.coords().to_vec()
+}
+
+/// Per-line metrics for a shaped [`TextLayout`], in the same units as the
+/// layout's font size.
+#[derive(Debug, Clone, Copy)]
+pub struct LineMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub baseline: f32,
+    pub line_height: f32,
+}
+
+/// A shaped, line-broken block of text produced by [`SimpleText::layout`].
+///
+/// Unlike the raw per-char pen loop this replaces, a `TextLayout` carries
+/// real shaping (kerning, ligatures, bidi) and line-breaking, and can answer
+/// hit-testing queries so text widgets can place carets and selections.
+pub struct TextLayout {
+    inner: Layout<Brush>,
+}
+
+impl TextLayout {
+    pub fn line_count(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Per-line ascent/descent/baseline/line-height, in layout order.
+    pub fn line_metrics(&self) -> impl Iterator<Item = LineMetrics> + '_ {
+        self.inner.lines().map(|line| {
+            let metrics = line.metrics();
+            LineMetrics {
+                ascent: metrics.ascent,
+                descent: metrics.descent,
+                baseline: metrics.baseline,
+                line_height: metrics.line_height,
+            }
+        })
+    }
+
+    /// The tight bounding box of the laid-out text, in local layout space.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0.0, 0.0, self.inner.width() as f64, self.inner.height() as f64)
+    }
+
+    /// Map a point in local layout space to the nearest byte offset in the
+    /// source text, for click-to-place-caret.
+    pub fn hit_test_point(&self, x: f32, y: f32) -> usize {
+        self.inner.hit_test_point(x, y).index
+    }
+
+    /// The caret rectangle (in local layout space) for a byte offset in the
+    /// source text, for drawing a cursor or selection edge.
+    pub fn cursor_rect(&self, byte_offset: usize) -> Rect {
+        let cursor = parley::Cursor::from_byte_index(
+            &self.inner,
+            byte_offset,
+            parley::Affinity::Downstream,
+        );
+        let (x0, y0, x1, y1) = cursor.geometry(&self.inner, 1.5);
+        Rect::new(x0 as f64, y0 as f64, x1 as f64, y1 as f64)
+    }
+}
+
+pub struct SimpleText {
+    font_cx: FontContext,
+    layout_cx: LayoutContext<Brush>,
+    glyph_atlas: GlyphAtlas,
+}
+
+impl SimpleText {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let mut font_cx = FontContext::new();
+        font_cx.collection.register_fonts(get_roboto_font().data.as_ref().to_vec());
+        Self {
+            font_cx,
+            layout_cx: LayoutContext::new(),
+            glyph_atlas: GlyphAtlas::new(device, GLYPH_ATLAS_SIZE),
+        }
+    }
+
+    /// Entry count, atlas occupancy, and eviction count for the glyph cache,
+    /// surfaced in the `Stats` overlay.
+    pub fn glyph_cache_stats(&self) -> GlyphCacheStats {
+        self.glyph_atlas.stats()
+    }
+
+    /// Shape and line-break `text` at `size`, wrapping at `max_width` (in the
+    /// same units) if given, producing positioned glyph runs a caller can
+    /// measure, hit-test, or hand to [`SimpleText::draw_layout`].
+    pub fn layout(&mut self, text: &str, size: f32, brush: Brush, max_width: Option<f32>) -> TextLayout {
+        let mut builder = self.layout_cx.ranged_builder(&mut self.font_cx, text, 1.0);
+        builder.push_default(StyleProperty::FontSize(size));
+        builder.push_default(StyleProperty::Brush(brush));
+        let mut layout: Layout<Brush> = builder.build(text);
+        layout.break_all_lines(max_width);
+        layout.align(max_width, Alignment::Start);
+        TextLayout { inner: layout }
+    }
+
+    /// Draw a previously shaped [`TextLayout`]'s glyph runs into `scene`,
+    /// positioned by `transform`, snapped to the device pixel grid at
+    /// `scale_factor` so text stays crisp across DPI changes (see
+    /// [`crate::glyph_atlas::snap_transform_to_pixel_grid`]).
+    ///
+    /// Each glyph is also looked up (rasterizing and caching on a miss) in
+    /// the shared glyph atlas via `queue`, so [`Self::glyph_cache_stats`]
+    /// reports real hit/miss/occupancy numbers for repeated frames of the
+    /// same text (the common case for an overlay like `Stats`). Actual glyph
+    /// compositing still goes through Vello's own `draw_glyphs` below, which
+    /// re-submits outlines as vector paths every frame regardless of cache
+    /// state — see the note on [`crate::glyph_atlas::GlyphAtlas`]. This is
+    /// cache instrumentation, not yet a rendering optimization.
+    pub fn draw_layout(
+        &mut self,
+        scene: &mut Scene,
+        layout: &TextLayout,
+        transform: Affine,
+        scale_factor: f64,
+        queue: &wgpu::Queue,
+    ) {
+        let transform = snap_transform_to_pixel_grid(transform, scale_factor);
+        for line in layout.inner.lines() {
+            for item in line.items() {
+                let PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                    continue;
+                };
+                let run = glyph_run.run();
+                let font = run.font();
+                for glyph in glyph_run.glyphs() {
+                    self.glyph_atlas.get_or_rasterize(
+                        queue,
+                        font,
+                        GlyphId::new(glyph.id),
+                        run.font_size(),
+                    );
+                }
+                scene
+                    .draw_glyphs(font)
+                    .brush(glyph_run.style().brush.clone())
+                    .transform(transform)
+                    .font_size(run.font_size())
+                    .normalized_coords(run.normalized_coords())
+                    .draw(
+                        Fill::NonZero,
+                        glyph_run.glyphs().map(|g| Glyph {
+                            id: g.id as u32,
+                            x: g.x,
+                            y: g.y,
+                        }),
+                    );
+            }
+        }
+    }
+
+    /// Convenience one-shot: shape a single unwrapped run of `text` and draw
+    /// it immediately. Kept for callers that don't need the full `TextLayout`
+    /// (measurement, multi-line, hit-testing).
+    #[allow(clippy::too_many_arguments)]
     pub fn add(
         &mut self,
         scene: &mut Scene,
@@ -28,51 +263,71 @@ impl SimpleText {
         size: f32,
         brush: Option<&Brush>,
         transform: Affine,
+        scale_factor: f64,
+        queue: &wgpu::Queue,
         text: &str,
     ) {
-        let font = get_roboto_font();
-        let brush = brush.unwrap_or(&Brush::Solid(palette::css::WHITE));
-
-        let font_ref = to_font_ref(font).unwrap();
-        let font_size = skrifa::instance::Size::new(size);
-        let axes = font_ref.axes();
-        let variations: &[(&str, f32)] = &[];
-        let var_loc = axes.location(variations.iter().copied());
-        let charmap = font_ref.charmap();
-        let glyph_metrics = font_ref.glyph_metrics(font_size, &var_loc);
+        let brush = brush.cloned().unwrap_or(Brush::Solid(palette::css::WHITE));
+        let layout = self.layout(text, size, brush, None);
+        self.draw_layout(scene, &layout, transform, scale_factor, queue);
+    }
 
+    /// Draw `text` one glyph at a time, resolving each character against
+    /// `registry`'s fallback chain for `family` and applying `axes` to the
+    /// primary font's variable-font location. Mixed-script or emoji-adjacent
+    /// text that the primary family can't cover renders via the fallback
+    /// fonts instead of showing tofu.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_with_fallback(
+        &mut self,
+        scene: &mut Scene,
+        registry: &FontRegistry,
+        family: &str,
+        axes: &[AxisValue],
+        size: f32,
+        brush: Option<&Brush>,
+        transform: Affine,
+        scale_factor: f64,
+        queue: &wgpu::Queue,
+        text: &str,
+    ) {
+        let brush = brush.cloned().unwrap_or(Brush::Solid(palette::css::WHITE));
+        let transform = snap_transform_to_pixel_grid(transform, scale_factor);
         let mut pen_x = 0_f32;
 
-        scene
-            .draw_glyphs(font)
-            .font_size(size)
-            .transform(transform)
-            .normalized_coords(bytemuck::cast_slice(var_loc.coords()))
-            .brush(brush)
-            .draw(
-                Fill::NonZero,
-                text.chars().map(|ch| {
-                    let gid = charmap.map(ch).unwrap_or_default();
-                    let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-                    let x = pen_x;
-                    pen_x += advance;
-                    Glyph {
+        for ch in text.chars() {
+            let Some((font, gid)) = registry.resolve_glyph(family, ch) else {
+                continue;
+            };
+            let font_ref = to_font_ref(font).expect("invalid font data");
+            let coords = normalized_coords(font, axes);
+            let glyph_metrics =
+                font_ref.glyph_metrics(skrifa::instance::Size::new(size), &font_ref.axes().location(axes.iter().copied()));
+            let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
+            let x = pen_x;
+            pen_x += advance;
+
+            self.glyph_atlas.get_or_rasterize(queue, font, gid, size);
+
+            scene
+                .draw_glyphs(font)
+                .font_size(size)
+                .transform(transform)
+                .normalized_coords(bytemuck::cast_slice(&coords))
+                .brush(&brush)
+                .draw(
+                    Fill::NonZero,
+                    std::iter::once(Glyph {
                         id: gid.to_u32(),
                         x,
                         y: 0.0,
-                    }
-                }),
-            );
-    }
-}
-
-impl Default for SimpleText {
-    fn default() -> Self {
-        Self::new()
+                    }),
+                );
+        }
     }
 }
 
-fn to_font_ref(font: &FontData) -> Option<FontRef<'_>> {
+pub(crate) fn to_font_ref(font: &FontData) -> Option<FontRef<'_>> {
     let file_ref = FileRef::new(font.data.as_ref()).ok()?;
     match file_ref {
         FileRef::Font(font) => Some(font),
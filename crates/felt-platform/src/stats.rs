@@ -8,23 +8,40 @@ use vello::{AaConfig, Scene};
 
 const SLIDING_WINDOW_SIZE: usize = 100;
 
+/// A GPU buffer's reported occupancy, e.g. Vello's binning/ptcl/segment
+/// staging buffers, as a fraction of its allocated capacity.
+#[derive(Debug, Clone)]
+pub struct BufferUtilization {
+    pub name: &'static str,
+    pub used_fraction: f32,
+}
+
 #[derive(Debug)]
 pub struct Snapshot {
     pub fps: f64,
     pub frame_time_ms: f64,
     pub frame_time_min_ms: f64,
     pub frame_time_max_ms: f64,
+    /// GPU render time, if the adapter supports timestamp queries. A frame
+    /// or two stale relative to `frame_time_ms`, since readback is async.
+    pub gpu_time_ms: Option<f64>,
+    /// Vello bump-allocator buffer occupancy for the most recent completed frame.
+    pub buffer_utilization: Vec<BufferUtilization>,
 }
 
 impl Snapshot {
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_layer(
         &self,
         scene: &mut Scene,
         text: &mut SimpleText,
         viewport_size: (f64, f64),
-        samples: impl Iterator<Item = u64>,
+        mut samples: impl Iterator<Item = u64>,
+        mut gpu_samples: impl Iterator<Item = u64>,
         vsync: VSync,
         aa_config: AaConfig,
+        scale_factor: f64,
+        queue: &wgpu::Queue,
     ) {
         let (viewport_width, viewport_height) = viewport_size;
         let width = (viewport_width * 0.4).clamp(200., 600.);
@@ -43,7 +60,7 @@ impl Snapshot {
         );
 
         // Draw text labels
-        let labels = [
+        let mut labels = vec![
             format!("Frame Time: {:.2} ms", self.frame_time_ms),
             format!("Frame Time (min): {:.2} ms", self.frame_time_min_ms),
             format!("Frame Time (max): {:.2} ms", self.frame_time_max_ms),
@@ -65,6 +82,34 @@ impl Snapshot {
             ),
             format!("Resolution: {viewport_width}x{viewport_height}"),
         ];
+        if let Some(gpu_time_ms) = self.gpu_time_ms {
+            labels.push(format!("GPU Time: {:.2} ms", gpu_time_ms));
+        }
+        if self.buffer_utilization.is_empty() {
+            // Vello doesn't currently expose its bump-allocator buffer
+            // occupancy (see `renderer::sample_buffer_utilization`), so say
+            // so explicitly rather than silently omitting the row — a
+            // missing row reads as "nothing to report," not "can't report."
+            labels.push("Buffer utilization: not available".to_string());
+        }
+        for buf in &self.buffer_utilization {
+            labels.push(format!(
+                "{}: {:.0}% full",
+                buf.name,
+                buf.used_fraction * 100.
+            ));
+        }
+        let glyph_cache = text.glyph_cache_stats();
+        let glyph_atlas_pixels = (glyph_cache.atlas_width as u64) * (glyph_cache.atlas_height as u64);
+        let glyph_occupancy = if glyph_atlas_pixels > 0 {
+            glyph_cache.occupied_area as f64 / glyph_atlas_pixels as f64 * 100.
+        } else {
+            0.
+        };
+        labels.push(format!(
+            "Glyph cache: {} entries, {:.0}% atlas, {} evictions",
+            glyph_cache.entries, glyph_occupancy, glyph_cache.evictions
+        ));
 
         let text_height = height * 0.5 / (1 + labels.len()) as f64;
         let left_margin = width * 0.01;
@@ -76,6 +121,8 @@ impl Snapshot {
                 text_size,
                 Some(&Brush::Solid(palette::css::WHITE)),
                 offset * Affine::translate((left_margin, (i + 1) as f64 * text_height)),
+                scale_factor,
+                queue,
                 label,
             );
         }
@@ -85,23 +132,19 @@ impl Snapshot {
             text_size,
             Some(&Brush::Solid(palette::css::WHITE)),
             offset * Affine::translate((width * 0.67, text_height)),
+            scale_factor,
+            queue,
             &format!("FPS: {:.2}", self.fps),
         );
 
-        // Plot the samples with a bar graph
+        // Plot the samples with a bar graph. When GPU timing is available the
+        // CPU graph gives up a bottom strip to a second, smaller GPU graph.
         use PathEl::*;
         let left_padding = width * 0.05;
-        let graph_max_height = height * 0.5;
         let graph_max_width = width - 2. * (width * 0.01) - left_padding;
         let left_margin_padding = width * 0.01 + left_padding;
         let bar_extent = graph_max_width / (SLIDING_WINDOW_SIZE as f64);
         let bar_width = bar_extent * 0.4;
-        let bar = [
-            MoveTo((0., graph_max_height).into()),
-            LineTo((0., 0.).into()),
-            LineTo((bar_width, 0.).into()),
-            LineTo((bar_width, graph_max_height).into()),
-        ];
 
         let display_max = if self.frame_time_max_ms > 3. * self.frame_time_ms {
             round_up((1.33334 * self.frame_time_ms) as usize, 5) as f64
@@ -109,38 +152,70 @@ impl Snapshot {
             self.frame_time_max_ms
         };
 
-        for (i, sample) in samples.enumerate() {
-            let t = offset * Affine::translate((i as f64 * bar_extent, graph_max_height));
-            let sample_ms = ((sample as f64) * 0.001).min(display_max);
-            let h = sample_ms / display_max;
-            let s = Affine::scale_non_uniform(1., -h);
-
-            let color = match sample {
-                ..=16_667 => Color::from_rgb8(100, 143, 255), // 60fps
-                16_668..=33_334 => Color::from_rgb8(255, 176, 0), // 30fps
-                _ => Color::from_rgb8(220, 38, 127),          // <30fps
-            };
-
-            scene.fill(
-                Fill::NonZero,
-                t * Affine::translate((
-                    left_margin_padding,
-                    (1 + labels.len()) as f64 * text_height,
-                )) * s,
-                color,
-                None,
-                &bar,
+        let bar_graph_origin = (1 + labels.len()) as f64 * text_height;
+        let cpu_graph_height = if self.gpu_time_ms.is_some() {
+            height * 0.35
+        } else {
+            height * 0.5
+        };
+
+        let draw_bars = |scene: &mut Scene,
+                          samples: &mut dyn Iterator<Item = u64>,
+                          graph_height: f64,
+                          baseline_y: f64| {
+            let bar = [
+                MoveTo((0., graph_height).into()),
+                LineTo((0., 0.).into()),
+                LineTo((bar_width, 0.).into()),
+                LineTo((bar_width, graph_height).into()),
+            ];
+            for (i, sample) in samples.enumerate() {
+                let t = offset * Affine::translate((i as f64 * bar_extent, baseline_y));
+                let sample_ms = ((sample as f64) * 0.001).min(display_max);
+                let h = sample_ms / display_max;
+                let s = Affine::scale_non_uniform(1., -h);
+
+                let color = match sample {
+                    ..=16_667 => Color::from_rgb8(100, 143, 255), // 60fps
+                    16_668..=33_334 => Color::from_rgb8(255, 176, 0), // 30fps
+                    _ => Color::from_rgb8(220, 38, 127),          // <30fps
+                };
+
+                scene.fill(
+                    Fill::NonZero,
+                    t * Affine::translate((left_margin_padding, 0.)) * s,
+                    color,
+                    None,
+                    &bar,
+                );
+            }
+        };
+
+        draw_bars(
+            scene,
+            &mut samples,
+            cpu_graph_height,
+            bar_graph_origin + cpu_graph_height,
+        );
+
+        if self.gpu_time_ms.is_some() {
+            let gpu_graph_height = height * 0.15;
+            draw_bars(
+                scene,
+                &mut gpu_samples,
+                gpu_graph_height,
+                bar_graph_origin + cpu_graph_height + gpu_graph_height,
             );
         }
 
-        // Draw threshold markers
+        // Draw threshold markers against the CPU graph.
         let marker = [
-            MoveTo((0., graph_max_height).into()),
-            LineTo((graph_max_width, graph_max_height).into()),
+            MoveTo((0., cpu_graph_height).into()),
+            LineTo((graph_max_width, cpu_graph_height).into()),
         ];
 
         let thresholds = [8.33, 16.66, 33.33];
-        let thres_text_height = graph_max_height * 0.05;
+        let thres_text_height = cpu_graph_height * 0.05;
         let thres_text_height_2 = thres_text_height * 0.5;
         for t in thresholds.iter().filter(|&&t| t < display_max) {
             let y = t / display_max;
@@ -152,13 +227,19 @@ impl Snapshot {
                 offset
                     * Affine::translate((
                         left_margin,
-                        (2. - y) * graph_max_height + thres_text_height_2,
+                        bar_graph_origin + (1. - y) * cpu_graph_height + thres_text_height_2,
                     )),
+                scale_factor,
+                queue,
                 &format!("{t}"),
             );
             scene.stroke(
-                &Stroke::new(graph_max_height * 0.01),
-                offset * Affine::translate((left_margin_padding, (1. - y) * graph_max_height)),
+                &Stroke::new(cpu_graph_height * 0.01),
+                offset
+                    * Affine::translate((
+                        left_margin_padding,
+                        bar_graph_origin + (1. - y) * cpu_graph_height,
+                    )),
                 palette::css::WHITE,
                 None,
                 &marker,
@@ -171,7 +252,17 @@ pub struct Sample {
     pub frame_time_us: u64,
 }
 
-pub struct Stats {
+// GPU timestamp-query readback (see `renderer::GpuTimestampQuery`) lands a
+// frame or two after the `Sample` it logically corresponds to, since
+// `map_async` is asynchronous — so it's reported through `add_gpu_sample`
+// into its own `Ring` below rather than folded into `Sample` itself, which
+// would force the caller to either block on the readback or misattribute it
+// to the wrong frame.
+
+/// A fixed-size sliding-window accumulator, used independently for CPU and
+/// GPU timings so one missing/late GPU sample doesn't skew the CPU average.
+#[derive(Default)]
+struct Ring {
     count: usize,
     sum: u64,
     min: u64,
@@ -179,6 +270,59 @@ pub struct Stats {
     samples: VecDeque<u64>,
 }
 
+impl Ring {
+    fn new() -> Self {
+        Self {
+            min: u64::MAX,
+            max: u64::MIN,
+            samples: VecDeque::with_capacity(SLIDING_WINDOW_SIZE),
+            ..Default::default()
+        }
+    }
+
+    fn add(&mut self, micros: u64) {
+        let oldest = if self.count < SLIDING_WINDOW_SIZE {
+            self.count += 1;
+            None
+        } else {
+            self.samples.pop_front()
+        };
+
+        self.sum += micros;
+        self.samples.push_back(micros);
+
+        if let Some(oldest) = oldest {
+            self.sum -= oldest;
+        }
+
+        self.min = self.min.min(micros);
+        self.max = self.max.max(micros);
+    }
+
+    fn clear_min_and_max(&mut self) {
+        self.min = u64::MAX;
+        self.max = u64::MIN;
+    }
+
+    fn mean_ms(&self) -> f64 {
+        (self.sum as f64 / self.count.max(1) as f64) * 0.001
+    }
+
+    fn min_ms(&self) -> f64 {
+        self.min as f64 * 0.001
+    }
+
+    fn max_ms(&self) -> f64 {
+        self.max as f64 * 0.001
+    }
+}
+
+pub struct Stats {
+    cpu: Ring,
+    gpu: Ring,
+    buffer_utilization: Vec<BufferUtilization>,
+}
+
 impl Default for Stats {
     fn default() -> Self {
         Self::new()
@@ -188,52 +332,50 @@ impl Default for Stats {
 impl Stats {
     pub fn new() -> Self {
         Self {
-            count: 0,
-            sum: 0,
-            min: u64::MAX,
-            max: u64::MIN,
-            samples: VecDeque::with_capacity(SLIDING_WINDOW_SIZE),
+            cpu: Ring::new(),
+            gpu: Ring::new(),
+            buffer_utilization: Vec::new(),
         }
     }
 
     pub fn samples(&self) -> impl Iterator<Item = u64> + '_ {
-        self.samples.iter().copied()
+        self.cpu.samples.iter().copied()
+    }
+
+    pub fn gpu_samples(&self) -> impl Iterator<Item = u64> + '_ {
+        self.gpu.samples.iter().copied()
     }
 
     pub fn snapshot(&self) -> Snapshot {
-        let frame_time_ms = (self.sum as f64 / self.count as f64) * 0.001;
-        let fps = 1000. / frame_time_ms;
         Snapshot {
-            fps,
-            frame_time_ms,
-            frame_time_min_ms: self.min as f64 * 0.001,
-            frame_time_max_ms: self.max as f64 * 0.001,
+            fps: 1000. / self.cpu.mean_ms(),
+            frame_time_ms: self.cpu.mean_ms(),
+            frame_time_min_ms: self.cpu.min_ms(),
+            frame_time_max_ms: self.cpu.max_ms(),
+            gpu_time_ms: (self.gpu.count > 0).then(|| self.gpu.mean_ms()),
+            buffer_utilization: self.buffer_utilization.clone(),
         }
     }
 
     pub fn clear_min_and_max(&mut self) {
-        self.min = u64::MAX;
-        self.max = u64::MIN;
+        self.cpu.clear_min_and_max();
+        self.gpu.clear_min_and_max();
     }
 
     pub fn add_sample(&mut self, sample: Sample) {
-        let oldest = if self.count < SLIDING_WINDOW_SIZE {
-            self.count += 1;
-            None
-        } else {
-            self.samples.pop_front()
-        };
-
-        let micros = sample.frame_time_us;
-        self.sum += micros;
-        self.samples.push_back(micros);
+        self.cpu.add(sample.frame_time_us);
+    }
 
-        if let Some(oldest) = oldest {
-            self.sum -= oldest;
-        }
+    /// Record a GPU frame time, resolved asynchronously from a timestamp
+    /// query readback and so generally a frame or two stale relative to the
+    /// CPU sample it's reported alongside.
+    pub fn add_gpu_sample(&mut self, gpu_time_us: u64) {
+        self.gpu.add(gpu_time_us);
+    }
 
-        self.min = self.min.min(micros);
-        self.max = self.max.max(micros);
+    /// Record the most recently read back Vello bump-allocator occupancy.
+    pub fn set_buffer_utilization(&mut self, buffer_utilization: Vec<BufferUtilization>) {
+        self.buffer_utilization = buffer_utilization;
     }
 }
 
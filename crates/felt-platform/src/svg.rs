@@ -0,0 +1,70 @@
+//! Load an SVG document once and append its pre-encoded paths into a
+//! [`vello::Scene`] every frame, via `usvg` (parsing) and `vello_svg`
+//! (`usvg`-tree → `Scene` translation) — see [`App::mount_svg`].
+use vello::Scene;
+use vello::kurbo::{Affine, Size};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SvgError {
+    #[error("failed to parse SVG: {0}")]
+    Parse(#[from] usvg::Error),
+}
+
+/// A parsed SVG document, encoded into a [`Scene`] once up front so drawing
+/// it is just an `append` rather than a re-parse every frame. Cheap to clone
+/// (`Arc`-backed internally by `Scene`).
+#[derive(Clone)]
+pub struct SvgDocument {
+    scene: Scene,
+    /// The document's intrinsic size (its `viewBox`/width/height), in SVG
+    /// user units — what [`Self::fit_transform`] scales from.
+    size: Size,
+}
+
+impl SvgDocument {
+    /// Parse an SVG document from its XML source.
+    pub fn from_str(svg: &str) -> Result<Self, SvgError> {
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default())?;
+        Ok(Self::from_tree(&tree))
+    }
+
+    /// Parse an SVG document from raw bytes (UTF-8 XML).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SvgError> {
+        let tree = usvg::Tree::from_data(bytes, &usvg::Options::default())?;
+        Ok(Self::from_tree(&tree))
+    }
+
+    fn from_tree(tree: &usvg::Tree) -> Self {
+        let size = tree.size();
+        Self {
+            scene: vello_svg::render_tree(tree),
+            size: Size::new(size.width() as f64, size.height() as f64),
+        }
+    }
+
+    /// The document's intrinsic size, in SVG user units.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Append this document's encoded paths into `scene`, positioned by
+    /// `transform` (see [`Self::fit_transform`] for a reasonable default).
+    pub fn append_to(&self, scene: &mut Scene, transform: Affine) {
+        scene.append(&self.scene, Some(transform));
+    }
+
+    /// An `Affine` that scales this document to fit within `viewport` while
+    /// preserving its aspect ratio ("contain" fit), centering the result —
+    /// recompute this whenever the viewport changes (e.g. on
+    /// `WindowEvent::Resized`, or simply every frame as [`App::mount_svg`]
+    /// does, since that's cheap enough not to bother caching).
+    pub fn fit_transform(&self, viewport: Size) -> Affine {
+        if self.size.width <= 0.0 || self.size.height <= 0.0 {
+            return Affine::IDENTITY;
+        }
+        let scale = (viewport.width / self.size.width).min(viewport.height / self.size.height);
+        let offset_x = (viewport.width - self.size.width * scale) / 2.0;
+        let offset_y = (viewport.height - self.size.height * scale) / 2.0;
+        Affine::translate((offset_x, offset_y)) * Affine::scale(scale)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::draw::Rect;
+use std::cell::{Cell, RefCell};
+use std::hash::{Hash, Hasher};
+
+/// One widget's contribution to a frame's damage computation: its bounding
+/// box in scene (post-transform) space, plus a cheap fingerprint of whatever
+/// visual properties it painted from. Widgets in this crate are rebuilt from
+/// scratch every frame rather than retained (see `AppExtension::mount_ui`'s
+/// doc comment), so there's no stable widget identity to key off of —
+/// instead, the Nth [`DamageTracker::report`] call this frame is compared
+/// against the Nth call last frame. That's exact as long as the tree's shape
+/// (and paint order) didn't change; if it did, `len()` mismatches below and
+/// we fall back to treating the whole frame as damaged rather than risk
+/// misattributing one widget's fingerprint to another.
+#[derive(Clone, Copy, PartialEq)]
+struct Entry {
+    bbox: Rect,
+    fingerprint: u64,
+}
+
+/// Accumulates per-widget fingerprints during a paint pass and compares them
+/// against the previous frame's to compute the union of changed bounding
+/// boxes — the "damage rect" a host can use to decide whether a frame needs
+/// presenting at all, idling GPU present/vsync work when nothing changed.
+///
+/// This only gates `present()`, not the widget tree rebuild or scene paint
+/// that precede it: a fingerprint can't be computed without first rebuilding
+/// and painting the tree, so there's no way to know a frame is undamaged
+/// before doing that work — skipping the rebuild itself would need an
+/// explicit dirty signal from the app (akin to `RepaintHandle`, but
+/// negative) rather than anything this tracker can infer on its own.
+///
+/// Threaded through [`crate::PaintCtx`] the same way `layer_renderer` is:
+/// cheap to clone (`Rc`), optional (absent in contexts that don't repaint
+/// across multiple frames, like reftest snapshots), and silently ignored by
+/// a widget that never calls [`crate::PaintCtx::report_damage`].
+pub struct DamageTracker {
+    previous: RefCell<Vec<Entry>>,
+    current: RefCell<Vec<Entry>>,
+    force_full: Cell<bool>,
+}
+
+impl DamageTracker {
+    pub fn new() -> Self {
+        Self {
+            previous: RefCell::new(Vec::new()),
+            current: RefCell::new(Vec::new()),
+            // Nothing to compare the first frame against, so it always
+            // counts as fully damaged.
+            force_full: Cell::new(true),
+        }
+    }
+
+    /// Ask for the whole scene to count as damaged next frame, bypassing
+    /// fingerprint comparison entirely — e.g. after a theme change, or
+    /// anything else a widget's own fingerprint wouldn't reflect.
+    pub fn force_full_repaint(&self) {
+        self.force_full.set(true);
+    }
+
+    pub(crate) fn report(&self, bbox: Rect, fingerprint: u64) {
+        self.current.borrow_mut().push(Entry { bbox, fingerprint });
+    }
+
+    /// Finish this frame: compute the union of bounding boxes that changed
+    /// since the last call (or `None` if nothing did, meaning the caller can
+    /// skip presenting this frame), then roll this frame's entries into
+    /// `previous` for the next comparison.
+    pub fn end_frame(&self) -> Option<Rect> {
+        let current = self.current.borrow();
+        let previous = self.previous.borrow();
+
+        let damage = if self.force_full.get() || current.len() != previous.len() {
+            current.iter().map(|e| e.bbox).reduce(|a, b| a.union(b))
+        } else {
+            current
+                .iter()
+                .zip(previous.iter())
+                .fold(None, |damage, (cur, prev)| {
+                    if cur.fingerprint == prev.fingerprint && cur.bbox == prev.bbox {
+                        damage
+                    } else {
+                        let changed = cur.bbox.union(prev.bbox);
+                        Some(match damage {
+                            Some(d) => d.union(changed),
+                            None => changed,
+                        })
+                    }
+                })
+        };
+
+        drop(current);
+        drop(previous);
+        self.previous.replace(self.current.borrow().clone());
+        self.current.borrow_mut().clear();
+        self.force_full.set(false);
+        damage
+    }
+}
+
+impl Default for DamageTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Combine a widget's visual properties into a single fingerprint for
+/// [`crate::PaintCtx::report_damage`]. Not a general-purpose hash — just
+/// enough entropy to notice when any of `parts` changed since last frame.
+/// Feed in bit patterns for anything that isn't already a `u64` (e.g.
+/// `f64::to_bits`, or a fieldless enum variant cast `as u64`).
+pub fn fingerprint(parts: &[u64]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    parts.hash(&mut hasher);
+    hasher.finish()
+}
@@ -4,7 +4,8 @@ use vello::peniko;
 
 // Re-export core geometric types
 pub use kurbo::{
-    Affine, Arc, BezPath, Circle, Ellipse, Line, Point, Rect, RoundedRect, Size, Vec2,
+    Affine, Arc, BezPath, Circle, Ellipse, Line, Point, Rect, RoundedRect, RoundedRectRadii, Size,
+    Vec2,
 };
 
 // Color abstraction
@@ -28,6 +29,10 @@ impl Color {
     pub(crate) fn to_vello(&self) -> peniko::Color {
         peniko::Color::rgba8(self.r, self.g, self.b, self.a)
     }
+
+    pub(crate) fn fingerprint(&self) -> u64 {
+        crate::damage::fingerprint(&[self.r as u64, self.g as u64, self.b as u64, self.a as u64])
+    }
 }
 
 // Brush abstraction
@@ -44,16 +49,99 @@ impl Brush {
             Brush::Gradient(gradient) => gradient.to_vello(),
         }
     }
+
+    pub(crate) fn fingerprint(&self) -> u64 {
+        match self {
+            Brush::Solid(color) => crate::damage::fingerprint(&[0, color.fingerprint()]),
+            // Cheap rather than exact: a gradient's stops can change without
+            // its stop count changing, which this would miss. Good enough
+            // for the common case (swapping between a handful of named
+            // gradients) without walking every stop every frame.
+            Brush::Gradient(gradient) => {
+                crate::damage::fingerprint(&[1, gradient.inner.stops.len() as u64])
+            }
+        }
+    }
+}
+
+impl From<Color> for Brush {
+    fn from(color: Color) -> Self {
+        Brush::Solid(color)
+    }
+}
+
+impl From<Gradient> for Brush {
+    fn from(gradient: Gradient) -> Self {
+        Brush::Gradient(gradient)
+    }
+}
+
+// How a gradient behaves past its last/before its first color stop.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ExtendMode {
+    #[default]
+    Pad,
+    Repeat,
+    Reflect,
+}
+
+impl ExtendMode {
+    fn to_vello(self) -> peniko::Extend {
+        match self {
+            ExtendMode::Pad => peniko::Extend::Pad,
+            ExtendMode::Repeat => peniko::Extend::Repeat,
+            ExtendMode::Reflect => peniko::Extend::Reflect,
+        }
+    }
 }
 
 // Gradient abstraction
 #[derive(Clone, Debug)]
 pub struct Gradient {
-    // Simplified for now - can be expanded
     pub(crate) inner: peniko::Gradient,
 }
 
 impl Gradient {
+    /// A gradient that interpolates linearly between `start` and `end`.
+    pub fn linear(start: Point, end: Point) -> Self {
+        Self {
+            inner: peniko::Gradient::new_linear(start, end),
+        }
+    }
+
+    /// A gradient that radiates out from `center` to `radius`.
+    pub fn radial(center: Point, radius: f64) -> Self {
+        Self {
+            inner: peniko::Gradient::new_radial(center, radius as f32),
+        }
+    }
+
+    /// A gradient that sweeps around `center` from `start_angle` to `end_angle` (radians).
+    pub fn sweep(center: Point, start_angle: f64, end_angle: f64) -> Self {
+        Self {
+            inner: peniko::Gradient::new_sweep(center, start_angle as f32, end_angle as f32),
+        }
+    }
+
+    /// Append color stops, each an `(offset, color)` pair where `offset` runs `0.0..=1.0`.
+    pub fn with_stops(mut self, stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        let stops: Vec<peniko::ColorStop> = stops
+            .into_iter()
+            .map(|(offset, color)| peniko::ColorStop {
+                offset,
+                color: color.to_vello().into(),
+            })
+            .collect();
+        self.inner.stops = stops.into();
+        self
+    }
+
+    /// Set the behavior past the first/last color stop.
+    pub fn with_extend(mut self, extend: ExtendMode) -> Self {
+        self.inner.extend = extend.to_vello();
+        self
+    }
+
     pub(crate) fn to_vello(&self) -> peniko::Brush {
         peniko::Brush::Gradient(self.inner.clone())
     }
@@ -75,19 +163,110 @@ impl FillRule {
     }
 }
 
+// Line cap style
+#[derive(Clone, Copy, Debug, Default)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+impl LineCap {
+    fn to_vello(self) -> kurbo::Cap {
+        match self {
+            LineCap::Butt => kurbo::Cap::Butt,
+            LineCap::Round => kurbo::Cap::Round,
+            LineCap::Square => kurbo::Cap::Square,
+        }
+    }
+}
+
+// Line join style
+#[derive(Clone, Copy, Debug, Default)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl LineJoin {
+    fn to_vello(self) -> kurbo::Join {
+        match self {
+            LineJoin::Miter => kurbo::Join::Miter,
+            LineJoin::Round => kurbo::Join::Round,
+            LineJoin::Bevel => kurbo::Join::Bevel,
+        }
+    }
+}
+
 // Stroke style
 #[derive(Clone, Debug)]
 pub struct StrokeStyle {
     pub width: f64,
+    pub caps: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f64,
+    /// Alternating on/off lengths, e.g. `[4.0, 2.0]` for a dash-dot pattern.
+    /// Empty means a solid stroke.
+    pub dashes: Vec<f64>,
+    pub dash_offset: f64,
 }
 
 impl StrokeStyle {
     pub fn new(width: f64) -> Self {
-        Self { width }
+        Self {
+            width,
+            caps: LineCap::default(),
+            join: LineJoin::default(),
+            miter_limit: 4.0,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+
+    pub fn with_caps(mut self, caps: LineCap) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn with_miter_limit(mut self, miter_limit: f64) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    pub fn with_dashes(mut self, dashes: impl Into<Vec<f64>>, offset: f64) -> Self {
+        self.dashes = dashes.into();
+        self.dash_offset = offset;
+        self
     }
 
     pub(crate) fn to_vello(&self) -> kurbo::Stroke {
-        kurbo::Stroke::new(self.width)
+        let mut stroke = kurbo::Stroke::new(self.width)
+            .with_caps(self.caps.to_vello())
+            .with_join(self.join.to_vello())
+            .with_miter_limit(self.miter_limit);
+        if !self.dashes.is_empty() {
+            stroke = stroke.with_dashes(self.dash_offset, self.dashes.clone());
+        }
+        stroke
+    }
+
+    pub(crate) fn fingerprint(&self) -> u64 {
+        crate::damage::fingerprint(&[
+            self.width.to_bits(),
+            self.caps as u64,
+            self.join as u64,
+            self.miter_limit.to_bits(),
+            self.dashes.len() as u64,
+            self.dash_offset.to_bits(),
+        ])
     }
 }
 
@@ -137,6 +316,14 @@ impl BlendMode {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ImageDecodeError {
+    #[error("could not determine image format from the given bytes")]
+    UnknownFormat,
+    #[error("failed to decode image: {0}")]
+    Decode(#[from] image::ImageError),
+}
+
 // Image abstraction
 #[derive(Clone)]
 pub struct Image {
@@ -154,6 +341,14 @@ impl Image {
         Self { inner }
     }
 
+    /// Decode a PNG/JPEG/etc. byte buffer (format auto-detected) into an RGBA8 image.
+    pub fn from_encoded(bytes: &[u8]) -> Result<Self, ImageDecodeError> {
+        let format = image::guess_format(bytes).map_err(|_| ImageDecodeError::UnknownFormat)?;
+        let decoded = image::load_from_memory_with_format(bytes, format)?.to_rgba8();
+        let (width, height) = decoded.dimensions();
+        Ok(Self::new(decoded.into_raw(), width, height))
+    }
+
     pub fn from_vello(inner: peniko::Image) -> Self {
         Self { inner }
     }
@@ -162,3 +357,31 @@ impl Image {
         &self.inner
     }
 }
+
+/// Caches decoded [`Image`]s by their source bytes so repeated `draw_image`
+/// calls for the same asset (e.g. an icon used across many frames) don't
+/// re-decode every time.
+#[derive(Default)]
+pub struct ImageCache {
+    entries: std::collections::HashMap<u64, Image>,
+}
+
+impl ImageCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode `bytes` (if not already cached) and return the cached `Image`.
+    pub fn get_or_decode(&mut self, bytes: &[u8]) -> Result<&Image, ImageDecodeError> {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        let key = hasher.finish();
+
+        if !self.entries.contains_key(&key) {
+            let image = Image::from_encoded(bytes)?;
+            self.entries.insert(key, image);
+        }
+        Ok(self.entries.get(&key).unwrap())
+    }
+}
@@ -1,30 +1,37 @@
 use crate::Widget;
-use crate::draw::Color;
+use crate::draw::{Brush, Color, Gradient, RoundedRectRadii, StrokeStyle};
 use crate::element::{Element, IntoElement};
+use crate::layout::{AlignItems, FlexDirection, FlexSize, JustifyContent, Length, LayoutStyle};
 use crate::widget::container::Container;
 use vello::kurbo::{Size, Vec2};
 
 pub struct Div {
-    child: Option<Box<dyn crate::Widget>>,
+    children: Vec<Box<dyn crate::Widget>>,
     size: Option<Size>,
-    bg: Option<Color>,
-    border: Option<(Color, f64)>,
+    bg: Option<Brush>,
+    border: Option<(Brush, StrokeStyle)>,
+    corner_radii: RoundedRectRadii,
     offset: Vec2,
+    layout_style: LayoutStyle,
 }
 
 impl Div {
     pub fn new() -> Self {
         Self {
-            child: None,
+            children: Vec::new(),
             size: None,
             bg: None,
             border: None,
+            corner_radii: RoundedRectRadii::from_single_radius(0.0),
             offset: Vec2::ZERO,
+            layout_style: LayoutStyle::default(),
         }
     }
 
+    /// Appends a child. Calling this more than once arranges the children
+    /// with the flex properties set via `flex_direction`/`justify_content`/etc.
     pub fn child(mut self, child: impl IntoElement) -> Self {
-        self.child = Some(child.into_element().build());
+        self.children.push(child.into_element().build());
         self
     }
 
@@ -33,13 +40,94 @@ impl Div {
         self
     }
 
+    /// Size this div as a fraction of its parent instead of in absolute
+    /// pixels, e.g. `div().flex_size(FlexSize::full())`.
+    pub fn flex_size(mut self, size: FlexSize) -> Self {
+        self.layout_style.size = size;
+        self
+    }
+
+    pub fn width(mut self, length: impl Into<Length>) -> Self {
+        self.layout_style.size.width = Some(length.into());
+        self
+    }
+
+    pub fn height(mut self, length: impl Into<Length>) -> Self {
+        self.layout_style.size.height = Some(length.into());
+        self
+    }
+
+    pub fn flex_direction(mut self, flex_direction: FlexDirection) -> Self {
+        self.layout_style.flex_direction = flex_direction;
+        self
+    }
+
+    pub fn justify_content(mut self, justify_content: JustifyContent) -> Self {
+        self.layout_style.justify_content = justify_content;
+        self
+    }
+
+    pub fn align_items(mut self, align_items: AlignItems) -> Self {
+        self.layout_style.align_items = align_items;
+        self
+    }
+
+    pub fn gap(mut self, gap: f64) -> Self {
+        self.layout_style.gap = gap;
+        self
+    }
+
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.layout_style.padding = padding;
+        self
+    }
+
+    pub fn flex_grow(mut self, flex_grow: f32) -> Self {
+        self.layout_style.flex_grow = flex_grow;
+        self
+    }
+
+    pub fn flex_shrink(mut self, flex_shrink: f32) -> Self {
+        self.layout_style.flex_shrink = flex_shrink;
+        self
+    }
+
     pub fn bg(mut self, color: Color) -> Self {
-        self.bg = Some(color);
+        self.bg = Some(Brush::Solid(color));
+        self
+    }
+
+    pub fn background_gradient(mut self, gradient: Gradient) -> Self {
+        self.bg = Some(Brush::Gradient(gradient));
         self
     }
 
     pub fn border(mut self, color: Color, width: f64) -> Self {
-        self.border = Some((color, width));
+        self.border = Some((Brush::Solid(color), StrokeStyle::new(width)));
+        self
+    }
+
+    /// Border with a full [`StrokeStyle`] (caps, joins, dashes) and any brush.
+    pub fn border_stroke(mut self, brush: impl Into<Brush>, style: StrokeStyle) -> Self {
+        self.border = Some((brush.into(), style));
+        self
+    }
+
+    /// Uniform corner radius on all four corners.
+    pub fn border_radius(mut self, radius: f64) -> Self {
+        self.corner_radii = RoundedRectRadii::from_single_radius(radius);
+        self
+    }
+
+    /// Per-corner radii, in `top_left, top_right, bottom_right, bottom_left` order.
+    pub fn border_radii(
+        mut self,
+        top_left: f64,
+        top_right: f64,
+        bottom_right: f64,
+        bottom_left: f64,
+    ) -> Self {
+        self.corner_radii = RoundedRectRadii::new(top_left, top_right, bottom_right, bottom_left);
         self
     }
 
@@ -52,11 +140,13 @@ impl Div {
 impl Element for Div {
     fn build(self: Box<Self>) -> Box<dyn Widget> {
         Box::new(Container {
-            child: self.child,
+            children: self.children,
             background: self.bg,
             border: self.border,
+            corner_radii: self.corner_radii,
             offset: self.offset,
             size: self.size,
+            layout_style: self.layout_style,
         })
     }
 }
@@ -0,0 +1,227 @@
+//! A small flex layout subsystem built on `taffy`, used by [`crate::elements::Div`]
+//! to resolve relative/fractional sizing and arrange multiple children without
+//! hand-computed pixel offsets.
+use crate::draw::Vec2;
+use vello::kurbo::{Rect, Size};
+
+/// Either an absolute pixel length or a fraction of the parent's size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Length {
+    Px(f64),
+    /// `0.0..=1.0`, where `1.0` is 100% of the parent.
+    Percent(f64),
+}
+
+impl Length {
+    fn to_taffy(self) -> taffy::Dimension {
+        match self {
+            Length::Px(px) => taffy::Dimension::Length(px as f32),
+            Length::Percent(pct) => taffy::Dimension::Percent(pct as f32),
+        }
+    }
+}
+
+impl From<f64> for Length {
+    fn from(px: f64) -> Self {
+        Length::Px(px)
+    }
+}
+
+/// A width/height pair expressed in [`Length`]s rather than fixed pixels.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FlexSize {
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+}
+
+impl FlexSize {
+    /// 100% of the parent's width and height.
+    pub fn full() -> Self {
+        Self {
+            width: Some(Length::Percent(1.0)),
+            height: Some(Length::Percent(1.0)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FlexDirection {
+    #[default]
+    Row,
+    Column,
+    RowReverse,
+    ColumnReverse,
+}
+
+impl FlexDirection {
+    fn to_taffy(self) -> taffy::FlexDirection {
+        match self {
+            FlexDirection::Row => taffy::FlexDirection::Row,
+            FlexDirection::Column => taffy::FlexDirection::Column,
+            FlexDirection::RowReverse => taffy::FlexDirection::RowReverse,
+            FlexDirection::ColumnReverse => taffy::FlexDirection::ColumnReverse,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum JustifyContent {
+    #[default]
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl JustifyContent {
+    fn to_taffy(self) -> taffy::JustifyContent {
+        match self {
+            JustifyContent::Start => taffy::JustifyContent::Start,
+            JustifyContent::End => taffy::JustifyContent::End,
+            JustifyContent::Center => taffy::JustifyContent::Center,
+            JustifyContent::SpaceBetween => taffy::JustifyContent::SpaceBetween,
+            JustifyContent::SpaceAround => taffy::JustifyContent::SpaceAround,
+            JustifyContent::SpaceEvenly => taffy::JustifyContent::SpaceEvenly,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AlignItems {
+    #[default]
+    Stretch,
+    Start,
+    End,
+    Center,
+}
+
+impl AlignItems {
+    fn to_taffy(self) -> taffy::AlignItems {
+        match self {
+            AlignItems::Stretch => taffy::AlignItems::Stretch,
+            AlignItems::Start => taffy::AlignItems::Start,
+            AlignItems::End => taffy::AlignItems::End,
+            AlignItems::Center => taffy::AlignItems::Center,
+        }
+    }
+}
+
+/// The flex layout properties of a single node. Populated by `Div`'s builder
+/// methods and consumed by [`compute_layout`].
+#[derive(Clone, Copy, Debug)]
+pub struct LayoutStyle {
+    pub size: FlexSize,
+    pub flex_direction: FlexDirection,
+    pub justify_content: JustifyContent,
+    pub align_items: AlignItems,
+    pub gap: f64,
+    pub padding: f64,
+    pub flex_grow: f32,
+    pub flex_shrink: f32,
+}
+
+impl Default for LayoutStyle {
+    fn default() -> Self {
+        Self {
+            size: FlexSize::default(),
+            flex_direction: FlexDirection::default(),
+            justify_content: JustifyContent::default(),
+            align_items: AlignItems::default(),
+            gap: 0.0,
+            padding: 0.0,
+            flex_grow: 0.0,
+            // Matches taffy's own default — shrinking is normally on, so a
+            // div that never calls `.flex_shrink(..)` behaves the way it did
+            // before this field existed. `flex_shrink(0.0)` now means exactly
+            // what it says (disable shrinking) rather than being silently
+            // coerced back to this default.
+            flex_shrink: 1.0,
+        }
+    }
+}
+
+impl LayoutStyle {
+    fn to_taffy(self) -> taffy::Style {
+        let padding = taffy::Rect {
+            left: taffy::LengthPercentage::Length(self.padding as f32),
+            right: taffy::LengthPercentage::Length(self.padding as f32),
+            top: taffy::LengthPercentage::Length(self.padding as f32),
+            bottom: taffy::LengthPercentage::Length(self.padding as f32),
+        };
+        taffy::Style {
+            display: taffy::Display::Flex,
+            flex_direction: self.flex_direction.to_taffy(),
+            justify_content: Some(self.justify_content.to_taffy()),
+            align_items: Some(self.align_items.to_taffy()),
+            gap: taffy::Size {
+                width: taffy::LengthPercentage::Length(self.gap as f32),
+                height: taffy::LengthPercentage::Length(self.gap as f32),
+            },
+            padding,
+            flex_grow: self.flex_grow,
+            flex_shrink: self.flex_shrink,
+            size: taffy::Size {
+                width: self
+                    .size
+                    .width
+                    .map(Length::to_taffy)
+                    .unwrap_or(taffy::Dimension::Auto),
+                height: self
+                    .size
+                    .height
+                    .map(Length::to_taffy)
+                    .unwrap_or(taffy::Dimension::Auto),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Run a flex layout pass over `root` and its `children`, resolving every
+/// node's rect (origin + size) within `available`. The returned rects are in
+/// the same order as `children`, with `root`'s resolved rect returned separately.
+pub fn compute_layout(
+    root_style: LayoutStyle,
+    children_styles: &[LayoutStyle],
+    available: Size,
+) -> (Rect, Vec<Rect>) {
+    let mut tree: taffy::TaffyTree<()> = taffy::TaffyTree::new();
+
+    let leaves: Vec<_> = children_styles
+        .iter()
+        .map(|style| tree.new_leaf(style.to_taffy()).unwrap())
+        .collect();
+
+    let root = tree.new_with_children(root_style.to_taffy(), &leaves).unwrap();
+
+    tree.compute_layout(
+        root,
+        taffy::Size {
+            width: taffy::AvailableSpace::Definite(available.width as f32),
+            height: taffy::AvailableSpace::Definite(available.height as f32),
+        },
+    )
+    .unwrap();
+
+    let to_rect = |layout: &taffy::Layout| {
+        Rect::from_origin_size(
+            (layout.location.x as f64, layout.location.y as f64),
+            (layout.size.width as f64, layout.size.height as f64),
+        )
+    };
+
+    let root_rect = to_rect(tree.layout(root).unwrap());
+    let child_rects = leaves
+        .iter()
+        .map(|&leaf| to_rect(tree.layout(leaf).unwrap()))
+        .collect();
+
+    (root_rect, child_rects)
+}
+
+/// Convenience: the `Vec2` offset of a resolved child rect's origin.
+pub fn rect_offset(rect: &Rect) -> Vec2 {
+    Vec2::new(rect.x0, rect.y0)
+}
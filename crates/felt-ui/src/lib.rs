@@ -1,43 +1,223 @@
 use smallvec::SmallVec;
+use std::cell::RefCell;
+use std::rc::Rc;
 use vello::Scene;
+use vello::peniko::Mix;
 
+pub mod damage;
 pub mod draw;
 pub mod element;
 pub mod elements;
+pub mod layout;
+pub mod reftest;
+pub mod scene_file;
 pub mod widget;
 
+pub use damage::{DamageTracker, fingerprint};
 pub use draw::{
-    Affine, BlendMode, Brush, Circle, Color, FillRule, Image, Line, Point, Rect, RoundedRect, Size,
+    Affine, BlendMode, Brush, Circle, Color, ExtendMode, FillRule, Gradient, Image, ImageCache,
+    ImageDecodeError, Line, LineCap, LineJoin, Point, Rect, RoundedRect, RoundedRectRadii, Size,
     StrokeStyle, Vec2,
 };
 pub use element::{Element, IntoElement};
 pub use elements::div;
+pub use layout::{AlignItems, FlexDirection, FlexSize, JustifyContent, Length, LayoutStyle};
+pub use widget::cached_layer::{CachedLayer, cached_layer};
 pub use widget::canvas::{DrawContext, canvas};
-pub use widget::scroll::scroll_view;
+pub use widget::script::{ScriptError, ScriptWidget, script_engine};
+pub use widget::scroll::{ScrollState, scroll_view};
 
 pub type EntityId = u64;
 
-pub struct EventCtx;
+/// Threaded through a [`Widget::on_event`] call the same way [`PaintCtx`] is
+/// threaded through `paint`: each container reparents `position` into its
+/// child's local space and restores it on return, so a deeply nested widget
+/// still sees pointer coordinates relative to itself.
+pub struct EventCtx {
+    pub position: Point,
+    /// The size of the space available to the widget currently handling the
+    /// event — mirrors `PaintCtx::clip.size()`, which containers use to run
+    /// their own layout pass.
+    pub bounds: Size,
+    handled: bool,
+    repaint_requested: bool,
+}
+
+impl EventCtx {
+    pub fn new(position: Point, bounds: Size) -> Self {
+        Self {
+            position,
+            bounds,
+            handled: false,
+            repaint_requested: false,
+        }
+    }
+
+    /// Mark this event as handled. A container checks this after forwarding
+    /// to a child to decide whether it should also act (bubbling) or back off.
+    pub fn set_handled(&mut self) {
+        self.handled = true;
+    }
+
+    pub fn is_handled(&self) -> bool {
+        self.handled
+    }
+
+    /// Ask the host to schedule a repaint, e.g. because handling this event
+    /// changed some visible state like a scroll offset.
+    pub fn request_repaint(&mut self) {
+        self.repaint_requested = true;
+    }
+
+    pub fn repaint_requested(&self) -> bool {
+        self.repaint_requested
+    }
+}
+
 pub struct LayoutCtx;
 
+/// GPU-backed rasterization of a [`Scene`] into an offscreen RGBA8 image,
+/// threaded through [`PaintCtx`] so a widget that wants to cache a subtree's
+/// rendered pixels (see `widget::cached_layer`) doesn't need `felt-ui` to
+/// depend on `felt-platform`'s concrete `LayerRenderHandle` type directly —
+/// only this crate's own trait, implemented for it below to respect Rust's
+/// orphan rule without inverting the one-directional
+/// `felt-ui` -> `felt-platform` dependency.
+pub trait LayerRenderer {
+    /// Render `scene` into a `width`x`height` texture and read it back as
+    /// tightly packed (no row padding) RGBA8, or `None` if the render failed.
+    fn render_layer_to_rgba8(&self, scene: &Scene, width: u32, height: u32) -> Option<Vec<u8>>;
+}
+
+impl LayerRenderer for felt_platform::LayerRenderHandle {
+    fn render_layer_to_rgba8(&self, scene: &Scene, width: u32, height: u32) -> Option<Vec<u8>> {
+        felt_platform::LayerRenderHandle::render_layer_to_rgba8(self, scene, width, height)
+    }
+}
+
 pub struct PaintCtx {
     pub transform: Affine,
     pub clip: Rect,
+    /// Set by the host (e.g. [`AppExtension::mount_ui`]) when GPU-backed
+    /// offscreen rendering is available; `None` in contexts that can't
+    /// produce one (headless reftests, or before a window's GPU device has
+    /// been acquired). A widget that wants to cache a rendered subtree
+    /// should fall back to painting directly every frame when this is
+    /// `None`, the same way it would if caching were simply disabled.
+    pub layer_renderer: Option<Rc<dyn LayerRenderer>>,
+    /// Set by the host when frame-to-frame damage tracking is in effect;
+    /// `None` in contexts that don't repaint across multiple frames (e.g.
+    /// reftest snapshots). A widget that draws anything should call
+    /// [`Self::report_damage`] so an unchanging frame can be detected and
+    /// skipped — a widget that doesn't is simply invisible to damage
+    /// tracking, the same way it would be to caching if `layer_renderer`
+    /// were `None`.
+    pub damage: Option<Rc<DamageTracker>>,
 }
 
 impl PaintCtx {
-    pub fn paint_child(&mut self, child: &mut dyn Widget, scene: &mut Scene) {
-        // In a real system, we would adjust transform/clip here based on layout
-        child.paint(self, scene);
+    /// Run `f` with `self.transform` composed with `t` (`self.transform * t`),
+    /// restoring the previous transform on return. Pure transform
+    /// bookkeeping — doesn't touch `clip` or the scene, so it's cheap enough
+    /// to use for every child's layout offset.
+    pub fn with_transform(&mut self, t: Affine, f: impl FnOnce(&mut PaintCtx)) {
+        let previous_transform = self.transform;
+        self.transform = self.transform * t;
+        f(self);
+        self.transform = previous_transform;
     }
 
-    pub fn is_visible(&self, _rect: &Rect) -> bool {
-        true
+    /// Run `f` with `self.clip` intersected by `local_rect` (transformed into
+    /// the current coordinate space) and a matching clip layer pushed onto
+    /// `scene`, popping it and restoring the previous clip once `f` returns.
+    pub fn with_clip(
+        &mut self,
+        local_rect: Rect,
+        scene: &mut Scene,
+        f: impl FnOnce(&mut PaintCtx, &mut Scene),
+    ) {
+        let global_rect = self.transform.transform_rect_bbox(local_rect);
+        let previous_clip = self.clip;
+        self.clip = self.clip.intersect(global_rect);
+        scene.push_layer(Mix::Normal, 1.0, self.transform, &local_rect);
+        f(self, scene);
+        scene.pop_layer();
+        self.clip = previous_clip;
     }
+
+    /// Paint `child` offset by `offset` in the current coordinate space,
+    /// composing the translation into the transform passed down to it.
+    pub fn paint_child(&mut self, offset: Vec2, child: &mut dyn Widget, scene: &mut Scene) {
+        self.with_transform(Affine::translate(offset), |ctx| {
+            child.paint(ctx, scene);
+        });
+    }
+
+    /// Does `rect`, in the current local coordinate space, intersect the
+    /// active clip region? Lets a parent (e.g. a `ScrollView` with many
+    /// children) cull children that have scrolled fully offscreen.
+    pub fn is_visible(&self, rect: &Rect) -> bool {
+        let global_rect = self.transform.transform_rect_bbox(*rect);
+        global_rect.intersect(self.clip).area() > 0.0
+    }
+
+    /// Contribute this widget's bounding box (in the current local
+    /// coordinate space, transformed into scene space the same way
+    /// [`Self::is_visible`] does) and a fingerprint of its visual
+    /// properties (see [`damage::fingerprint`]) to the active
+    /// [`DamageTracker`], if any. A no-op when `self.damage` is `None`.
+    pub fn report_damage(&self, local_rect: Rect, fingerprint: u64) {
+        if let Some(tracker) = &self.damage {
+            let global_rect = self.transform.transform_rect_bbox(local_rect);
+            tracker.report(global_rect, fingerprint);
+        }
+    }
+}
+
+/// Which physical mouse button a [`Event::PointerDown`]/[`Event::PointerUp`]
+/// refers to. Kept separate from [`felt_platform::PointerButton`] so this
+/// crate's event model stays usable without a `felt_platform` window (e.g.
+/// from a headless reftest driving synthetic input).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Event {
-    // Stub
+    PointerDown {
+        position: Point,
+        button: PointerButton,
+    },
+    PointerMove {
+        position: Point,
+    },
+    PointerUp {
+        position: Point,
+        button: PointerButton,
+    },
+    /// Wheel/trackpad scroll delta, carrying the pointer position so nested
+    /// `scroll_view`s hit-test like any other pointer event.
+    Wheel {
+        position: Point,
+        delta: Vec2,
+    },
+}
+
+impl Event {
+    /// The pointer position carried by every variant, used to seed
+    /// [`EventCtx::position`] at the root of a [`dispatch_event`] call.
+    fn position(&self) -> Point {
+        match self {
+            Event::PointerDown { position, .. }
+            | Event::PointerMove { position }
+            | Event::PointerUp { position, .. }
+            | Event::Wheel { position, .. } => *position,
+        }
+    }
 }
 
 pub struct BoxConstraints {
@@ -45,39 +225,181 @@ pub struct BoxConstraints {
     pub max: Size,
 }
 
+/// Build a hit-test path into `root` and deliver `event` along it, innermost
+/// widget first: each container (`Container`, `ScrollView`) forwards to
+/// whichever child's transformed bounds contain `ctx.position` before
+/// deciding whether to also handle the event itself, so a descendant calling
+/// [`EventCtx::set_handled`] stops it from bubbling further. `width`/`height`
+/// seed the root's available size, the same way [`PaintCtx::clip`] is seeded
+/// at the top of a `paint` call.
+pub fn dispatch_event(root: &mut dyn Widget, width: u32, height: u32, event: &Event) -> EventCtx {
+    let mut ctx = EventCtx::new(
+        event.position(),
+        Size::new(width as f64, height as f64),
+    );
+    root.on_event(&mut ctx, event);
+    ctx
+}
+
 pub trait Widget {
     fn on_event(&mut self, _ctx: &mut EventCtx, _event: &Event) {}
     fn layout(&mut self, _ctx: &mut LayoutCtx, _bc: &BoxConstraints) -> Size {
         Size::ZERO
     }
+    /// Advance any time-based animation this widget is driving (e.g. a
+    /// [`ScrollState`] chasing its target offset), `dt` seconds since the
+    /// last call. Only meaningful for a widget instance that is retained
+    /// across frames rather than rebuilt from scratch each time — most of
+    /// this crate's widgets are stateless and leave this at its default.
+    fn update(&mut self, _dt: f64) {}
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene);
     fn children(&self) -> SmallVec<[EntityId; 4]> {
         SmallVec::new()
     }
+    /// This widget's flex layout properties, consumed by a parent
+    /// [`Container`](crate::widget::container::Container) running a flex pass
+    /// over its children. Widgets that don't participate in flex layout can
+    /// leave this at its default.
+    fn layout_style(&self) -> layout::LayoutStyle {
+        layout::LayoutStyle::default()
+    }
+}
+
+/// Returned by [`AppExtension::mount_ui`] so a caller can force a full
+/// repaint outside the normal per-frame fingerprint comparison — e.g. after
+/// swapping a theme, which doesn't change any individual widget's own
+/// properties across rebuilds but does change how they're drawn.
+pub struct RepaintHandle(Rc<DamageTracker>);
+
+impl RepaintHandle {
+    pub fn force_full_repaint(&self) {
+        self.0.force_full_repaint();
+    }
 }
 
 pub trait AppExtension {
-    fn mount_ui<F, E>(&mut self, builder: F)
+    fn mount_ui<F, E>(&mut self, builder: F) -> RepaintHandle
     where
         F: FnMut() -> E + 'static,
         E: IntoElement;
 }
 
+/// Translate a raw [`felt_platform::InputEvent`] into this crate's
+/// platform-agnostic [`Event`]. Public so a caller driving its own retained
+/// widget tree (bypassing [`AppExtension::mount_ui`]'s rebuild-every-dispatch
+/// convention) can still reuse this crate's input translation before calling
+/// [`dispatch_event`] directly.
+pub fn translate_input_event(input: felt_platform::InputEvent) -> Event {
+    use felt_platform::InputEvent as Raw;
+    fn button(b: felt_platform::PointerButton) -> PointerButton {
+        match b {
+            felt_platform::PointerButton::Left => PointerButton::Left,
+            felt_platform::PointerButton::Right => PointerButton::Right,
+            felt_platform::PointerButton::Middle => PointerButton::Middle,
+            felt_platform::PointerButton::Other(code) => PointerButton::Other(code),
+        }
+    }
+    match input {
+        Raw::PointerMoved { x, y } => Event::PointerMove {
+            position: Point::new(x, y),
+        },
+        Raw::PointerDown { x, y, button: b } => Event::PointerDown {
+            position: Point::new(x, y),
+            button: button(b),
+        },
+        Raw::PointerUp { x, y, button: b } => Event::PointerUp {
+            position: Point::new(x, y),
+            button: button(b),
+        },
+        Raw::Wheel {
+            x,
+            y,
+            delta_x,
+            delta_y,
+        } => Event::Wheel {
+            position: Point::new(x, y),
+            delta: Vec2::new(delta_x, delta_y),
+        },
+    }
+}
+
 impl AppExtension for felt_platform::App {
-    fn mount_ui<F, E>(&mut self, mut builder: F)
+    fn mount_ui<F, E>(&mut self, builder: F) -> RepaintHandle
     where
         F: FnMut() -> E + 'static,
         E: IntoElement,
     {
-        self.mount(move |scene, width, height| {
-            let mut root_widget = builder().into_element().build();
+        // Shared so both the paint callback and the input callback can
+        // rebuild the same immediate-mode tree from the same closure — this
+        // crate's immediate-mode widgets are rebuilt from scratch every
+        // frame/dispatch rather than retained, per `ScrollState`'s doc
+        // comment; a builder that needs state to persist across dispatches
+        // (e.g. a scroll offset nudged by a wheel event) should hold that
+        // state itself (in an `Rc<RefCell<_>>` captured by this closure) and
+        // feed it back in via `.offset(..)`, the same way it would for
+        // frame-driven animation.
+        let builder = Rc::new(RefCell::new(builder));
+        let window_size = Rc::new(RefCell::new((0u32, 0u32)));
+        let damage_tracker = Rc::new(DamageTracker::new());
+
+        let paint_builder = Rc::clone(&builder);
+        let paint_window_size = Rc::clone(&window_size);
+        let paint_damage_tracker = Rc::clone(&damage_tracker);
+        self.mount(move |scene, width, height, layer_renderer| {
+            *paint_window_size.borrow_mut() = (width, height);
+            let mut root_widget = paint_builder.borrow_mut()().into_element().build();
 
+            let layer_renderer: Option<Rc<dyn LayerRenderer>> = layer_renderer
+                .map(|handle| Rc::new(handle) as Rc<dyn LayerRenderer>);
             let mut ctx = PaintCtx {
                 transform: Affine::IDENTITY,
                 clip: Rect::new(0.0, 0.0, width as f64, height as f64),
+                layer_renderer,
+                damage: Some(Rc::clone(&paint_damage_tracker)),
             };
 
             root_widget.paint(&mut ctx, scene);
+
+            // `None` means nothing any widget reported changed since last
+            // frame — the host can skip presenting this frame and idle the
+            // GPU until an input event or `force_full_repaint` wakes it up
+            // again.
+            //
+            // This only decides *whether* to present, not whether to rebuild
+            // or paint: the damage rect is a comparison against *this*
+            // frame's fingerprints, which only exist once the tree above has
+            // already been rebuilt and painted. Narrowing what Vello shades
+            // within a presented frame to just the damage rect has the same
+            // problem one level deeper — by the time `end_frame` returns a
+            // rect, the scene for this frame has already been fully encoded,
+            // so there's nothing left to clip. Either would need a
+            // fundamentally different scheme (e.g. diffing against a
+            // previous frame's *retained* tree, or an explicit per-widget
+            // dirty signal from the app) rather than this fingerprint-after-
+            // the-fact comparison. For a mostly-static UI, skip-present is
+            // still most of the win: the CPU cost of one rebuild+paint is
+            // far cheaper than a GPU present/vsync wait.
+            paint_damage_tracker.end_frame().is_some()
         });
+
+        let event_builder = Rc::clone(&builder);
+        let event_window_size = Rc::clone(&window_size);
+        let event_damage_tracker = Rc::clone(&damage_tracker);
+        self.on_input(move |input| {
+            let (width, height) = *event_window_size.borrow();
+            let event = translate_input_event(input);
+            let mut root_widget = event_builder.borrow_mut()().into_element().build();
+            let ctx = dispatch_event(root_widget.as_mut(), width, height, &event);
+
+            // A widget that asked for a repaint (e.g. a `ScrollView` nudged
+            // by a wheel event) may have changed state that isn't reflected
+            // in any fingerprint this crate knows how to compute — force a
+            // full repaint rather than risk the next frame being skipped.
+            if ctx.repaint_requested() {
+                event_damage_tracker.force_full_repaint();
+            }
+        });
+
+        RepaintHandle(damage_tracker)
     }
 }
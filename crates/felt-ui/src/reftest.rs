@@ -0,0 +1,131 @@
+//! A reftest runner: renders a serialized [`crate::scene_file::SceneFile`]
+//! headless and compares the result, pixel for pixel, against a reference
+//! PNG. Gives `Container`/`ScrollView`'s paint code a deterministic visual
+//! regression test instead of relying on eyeballing the interactive demos.
+use crate::scene_file::{SceneFile, SceneFileError};
+use crate::{Affine, PaintCtx, Rect, Widget};
+use felt_platform::headless::HeadlessRenderer;
+use image::{ImageBuffer, Rgba};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReftestError {
+    #[error("failed to read scene file {path}: {source}")]
+    ReadScene {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    ParseScene(#[from] SceneFileError),
+    #[error("failed to decode reference image: {0}")]
+    DecodeReference(#[from] image::ImageError),
+    #[error("rendered size {actual:?} does not match reference size {expected:?}")]
+    SizeMismatch {
+        actual: (u32, u32),
+        expected: (u32, u32),
+    },
+    #[error("rendered image differs from the reference in {differing_pixels} pixel(s)")]
+    PixelMismatch {
+        differing_pixels: usize,
+        diff: DiffImage,
+    },
+    #[error(transparent)]
+    Render(#[from] felt_platform::renderer::RendererError),
+}
+
+/// A per-pixel absolute-difference visualization, written alongside a failed
+/// reftest so a human can see what changed.
+pub struct DiffImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl DiffImage {
+    pub fn save_png(&self, path: &str) -> Result<(), image::ImageError> {
+        let buffer: ImageBuffer<Rgba<u8>, _> =
+            ImageBuffer::from_raw(self.width, self.height, self.data.clone()).unwrap();
+        buffer.save(path)
+    }
+}
+
+/// Render `scene_path` headless and compare it against `reference_png_path`,
+/// allowing each RGBA channel to differ by up to `tolerance`.
+pub async fn run_reftest(
+    scene_path: &str,
+    reference_png_path: &str,
+    tolerance: u8,
+) -> Result<(), ReftestError> {
+    let source =
+        std::fs::read_to_string(scene_path).map_err(|source| ReftestError::ReadScene {
+            path: scene_path.to_string(),
+            source,
+        })?;
+    let scene_file = SceneFile::from_ron(&source)?;
+
+    let reference = image::open(reference_png_path)?.to_rgba8();
+    let (width, height) = reference.dimensions();
+
+    let mut root = scene_file.build();
+    let mut scene = vello::Scene::new();
+    let mut ctx = PaintCtx {
+        transform: Affine::IDENTITY,
+        clip: Rect::new(0.0, 0.0, width as f64, height as f64),
+        // A reftest snapshot has no `App`/GPU device of its own to render a
+        // cached layer's subtree into — any `CachedLayer` widget just falls
+        // back to painting directly every time.
+        layer_renderer: None,
+        // A single one-off snapshot has no previous frame to diff against.
+        damage: None,
+    };
+    root.paint(&mut ctx, &mut scene);
+
+    let mut renderer = HeadlessRenderer::new().await?;
+    let rendered = renderer.render_to_image(
+        &scene,
+        width,
+        height,
+        vello::peniko::color::palette::css::WHITE,
+    )?;
+
+    if (rendered.width, rendered.height) != (width, height) {
+        return Err(ReftestError::SizeMismatch {
+            actual: (rendered.width, rendered.height),
+            expected: (width, height),
+        });
+    }
+
+    let mut diff_data = vec![0u8; rendered.data.len()];
+    let mut differing_pixels = 0;
+    for (i, (actual, expected)) in rendered
+        .data
+        .chunks_exact(4)
+        .zip(reference.as_raw().chunks_exact(4))
+        .enumerate()
+    {
+        let mut differs = false;
+        for c in 0..4 {
+            let delta = actual[c].abs_diff(expected[c]);
+            diff_data[i * 4 + c] = delta;
+            if delta > tolerance {
+                differs = true;
+            }
+        }
+        diff_data[i * 4 + 3] = 255; // keep the diff image itself opaque
+        if differs {
+            differing_pixels += 1;
+        }
+    }
+
+    if differing_pixels > 0 {
+        return Err(ReftestError::PixelMismatch {
+            differing_pixels,
+            diff: DiffImage {
+                width,
+                height,
+                data: diff_data,
+            },
+        });
+    }
+
+    Ok(())
+}
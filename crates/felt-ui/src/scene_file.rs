@@ -0,0 +1,179 @@
+//! An on-disk description of a widget tree, read (and written) as RON,
+//! mirroring what wrench's `yaml_frame_reader` does for WebRender: a text
+//! fixture that builds a real, paintable scene without a Rust call site.
+//! This is the authoring format for reftest fixtures — see
+//! [`crate::reftest`] for how a fixture is rendered and compared.
+use crate::draw::Color;
+use crate::element::IntoElement;
+use crate::elements::div;
+use crate::layout::{AlignItems, FlexDirection, FlexSize, JustifyContent, Length};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SceneFileError {
+    #[error("failed to parse scene file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("failed to serialize scene file: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// Root of a serialized scene: a single tree of [`NodeDescriptor`]s built
+/// with [`div`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneFile {
+    pub root: NodeDescriptor,
+}
+
+impl SceneFile {
+    pub fn from_ron(source: &str) -> Result<Self, SceneFileError> {
+        Ok(ron::from_str(source)?)
+    }
+
+    pub fn to_ron(&self) -> Result<String, SceneFileError> {
+        Ok(ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?)
+    }
+
+    /// Build the real widget tree this file describes.
+    pub fn build(&self) -> Box<dyn crate::Widget> {
+        self.root.clone().into_element().build()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ColorDescriptor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    #[serde(default = "default_alpha")]
+    pub a: u8,
+}
+
+fn default_alpha() -> u8 {
+    255
+}
+
+impl From<ColorDescriptor> for Color {
+    fn from(c: ColorDescriptor) -> Self {
+        Color::rgba(c.r, c.g, c.b, c.a)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum LengthDescriptor {
+    #[default]
+    Auto,
+    Px(f64),
+    Percent(f64),
+}
+
+impl LengthDescriptor {
+    fn to_length(self) -> Option<Length> {
+        match self {
+            LengthDescriptor::Auto => None,
+            LengthDescriptor::Px(px) => Some(Length::Px(px)),
+            LengthDescriptor::Percent(pct) => Some(Length::Percent(pct)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum FlexDirectionDescriptor {
+    #[default]
+    Row,
+    Column,
+    RowReverse,
+    ColumnReverse,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum JustifyContentDescriptor {
+    #[default]
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum AlignItemsDescriptor {
+    #[default]
+    Stretch,
+    Start,
+    End,
+    Center,
+}
+
+/// A single node in a serialized widget tree: a [`div`] with an optional
+/// background/border, a fixed or flex size, layout properties, and children.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeDescriptor {
+    #[serde(default)]
+    pub background: Option<ColorDescriptor>,
+    #[serde(default)]
+    pub border: Option<(ColorDescriptor, f64)>,
+    #[serde(default)]
+    pub offset: (f64, f64),
+    #[serde(default)]
+    pub width: LengthDescriptor,
+    #[serde(default)]
+    pub height: LengthDescriptor,
+    #[serde(default)]
+    pub flex_direction: FlexDirectionDescriptor,
+    #[serde(default)]
+    pub justify_content: JustifyContentDescriptor,
+    #[serde(default)]
+    pub align_items: AlignItemsDescriptor,
+    #[serde(default)]
+    pub gap: f64,
+    #[serde(default)]
+    pub padding: f64,
+    #[serde(default)]
+    pub children: Vec<NodeDescriptor>,
+}
+
+impl IntoElement for NodeDescriptor {
+    fn into_element(self) -> Box<dyn crate::element::Element> {
+        let mut el = div()
+            .flex_size(FlexSize {
+                width: self.width.to_length(),
+                height: self.height.to_length(),
+            })
+            .offset(vello::kurbo::Vec2::new(self.offset.0, self.offset.1))
+            .flex_direction(match self.flex_direction {
+                FlexDirectionDescriptor::Row => FlexDirection::Row,
+                FlexDirectionDescriptor::Column => FlexDirection::Column,
+                FlexDirectionDescriptor::RowReverse => FlexDirection::RowReverse,
+                FlexDirectionDescriptor::ColumnReverse => FlexDirection::ColumnReverse,
+            })
+            .justify_content(match self.justify_content {
+                JustifyContentDescriptor::Start => JustifyContent::Start,
+                JustifyContentDescriptor::End => JustifyContent::End,
+                JustifyContentDescriptor::Center => JustifyContent::Center,
+                JustifyContentDescriptor::SpaceBetween => JustifyContent::SpaceBetween,
+                JustifyContentDescriptor::SpaceAround => JustifyContent::SpaceAround,
+                JustifyContentDescriptor::SpaceEvenly => JustifyContent::SpaceEvenly,
+            })
+            .align_items(match self.align_items {
+                AlignItemsDescriptor::Stretch => AlignItems::Stretch,
+                AlignItemsDescriptor::Start => AlignItems::Start,
+                AlignItemsDescriptor::End => AlignItems::End,
+                AlignItemsDescriptor::Center => AlignItems::Center,
+            })
+            .gap(self.gap)
+            .padding(self.padding);
+
+        if let Some(background) = self.background {
+            el = el.bg(background.into());
+        }
+        if let Some((color, width)) = self.border {
+            el = el.border(color.into(), width);
+        }
+        for child in self.children {
+            el = el.child(child);
+        }
+
+        el.into_element()
+    }
+}
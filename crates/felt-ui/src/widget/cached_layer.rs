@@ -0,0 +1,157 @@
+use crate::draw::Image;
+use crate::{EntityId, Event, EventCtx, LayerRenderer, PaintCtx, Widget};
+use smallvec::SmallVec;
+use vello::Scene;
+use vello::kurbo::{Affine, Point, Rect, Size};
+
+struct CachedImage {
+    image: Image,
+    content_hash: u64,
+    width: u32,
+    height: u32,
+}
+
+/// Wraps `child`, rasterizing it into an offscreen [`Image`] via
+/// [`PaintCtx::layer_renderer`] and reusing that image across frames instead
+/// of re-encoding the subtree into the parent scene every time — worthwhile
+/// for a subtree that's expensive to build (e.g. lots of small shapes) but
+/// rarely changes.
+///
+/// This crate has no automatic way to tell whether a subtree's visual
+/// output changed, so the caller supplies `content_hash` directly: hash
+/// whatever inputs you used to build `child` (the same way you'd pick a key
+/// for a list item), the same way [`crate::draw::ImageCache`] hashes an
+/// image's source bytes rather than its decoded pixels. The cached image is
+/// re-rendered whenever `content_hash` or `size` changes.
+pub struct CachedLayer {
+    pub size: Size,
+    pub content_hash: u64,
+    pub child: Box<dyn Widget>,
+    cached: Option<CachedImage>,
+}
+
+impl CachedLayer {
+    pub fn new(size: Size, content_hash: u64, child: impl Widget + 'static) -> Self {
+        Self {
+            size,
+            content_hash,
+            child: Box::new(child),
+            cached: None,
+        }
+    }
+}
+
+/// `vello::Renderer::render_to_texture` writes premultiplied-alpha pixels
+/// (matching its internal compositing), but `peniko::Image` — like any
+/// regularly-decoded PNG/JPEG loaded through [`Image::from_encoded`]) —
+/// expects straight alpha, so dividing each RGB channel by its alpha undoes
+/// the premultiplication and lets a cached layer composite identically to
+/// the same content painted directly. This does *not* touch the sRGB
+/// transfer function itself: the bytes stay gamma-encoded both before and
+/// after, since `Format::Rgba8` is already interpreted as sRGB-encoded
+/// straight alpha everywhere else in this crate — only the premultiplication
+/// needs undoing here.
+fn unpremultiply(rgba8: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(rgba8.len());
+    for texel in rgba8.chunks_exact(4) {
+        let (r, g, b, a) = (texel[0], texel[1], texel[2], texel[3]);
+        if a == 0 {
+            out.extend_from_slice(&[0, 0, 0, 0]);
+            continue;
+        }
+        let unpremultiply_channel =
+            |c: u8| -> u8 { ((c as f32 / a as f32) * 255.0).round().clamp(0.0, 255.0) as u8 };
+        out.extend_from_slice(&[
+            unpremultiply_channel(r),
+            unpremultiply_channel(g),
+            unpremultiply_channel(b),
+            a,
+        ]);
+    }
+    out
+}
+
+impl Widget for CachedLayer {
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) {
+        self.child.on_event(ctx, event);
+    }
+
+    fn update(&mut self, dt: f64) {
+        self.child.update(dt);
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let rect = Rect::from_origin_size(Point::ORIGIN, self.size);
+        if !ctx.is_visible(&rect) {
+            return;
+        }
+
+        // `content_hash` already is this widget's fingerprint — it's the
+        // caller-supplied signal that the subtree's visual output changed,
+        // exactly what damage tracking wants, so there's no need to recompute
+        // one from `self.size` separately.
+        ctx.report_damage(rect, crate::damage::fingerprint(&[self.content_hash]));
+
+        let Some(layer_renderer) = &ctx.layer_renderer else {
+            // No GPU layer-rendering capability available this frame (e.g. a
+            // headless reftest snapshot, or no device acquired yet) — fall
+            // back to painting the subtree directly, same as an uncached
+            // widget.
+            self.child.paint(ctx, scene);
+            return;
+        };
+
+        let width = self.size.width.ceil().max(1.0) as u32;
+        let height = self.size.height.ceil().max(1.0) as u32;
+
+        let is_stale = !matches!(
+            &self.cached,
+            Some(cached)
+                if cached.content_hash == self.content_hash
+                    && cached.width == width
+                    && cached.height == height
+        );
+
+        if is_stale {
+            // Rendered at a fixed content resolution, unaffected by the
+            // current transform/clip stack — only the `draw_image` fill
+            // below applies `ctx.transform` when compositing the result.
+            let mut layer_scene = Scene::new();
+            let mut layer_ctx = PaintCtx {
+                transform: Affine::IDENTITY,
+                clip: rect,
+                layer_renderer: ctx.layer_renderer.clone(),
+                // The cache itself is already this widget's contribution to
+                // the outer damage tracker (reported above); the offscreen
+                // render below doesn't need to feed it too.
+                damage: None,
+            };
+            self.child.paint(&mut layer_ctx, &mut layer_scene);
+
+            if let Some(rgba8) = layer_renderer.render_layer_to_rgba8(&layer_scene, width, height)
+            {
+                self.cached = Some(CachedImage {
+                    image: Image::new(unpremultiply(&rgba8), width, height),
+                    content_hash: self.content_hash,
+                    width,
+                    height,
+                });
+            }
+        }
+
+        match &self.cached {
+            Some(cached) => scene.draw_image(cached.image.to_vello(), ctx.transform),
+            // Rendering failed (e.g. the readback itself errored); fall back
+            // to direct painting rather than showing nothing this frame.
+            None => self.child.paint(ctx, scene),
+        }
+    }
+
+    fn children(&self) -> SmallVec<[EntityId; 4]> {
+        SmallVec::new()
+    }
+}
+
+pub fn cached_layer(size: Size, content_hash: u64, child: impl Widget + 'static) -> CachedLayer {
+    CachedLayer::new(size, content_hash, child)
+}
@@ -1,9 +1,9 @@
 use crate::draw::{Affine, BlendMode, Brush, FillRule, Point, Rect, Size, StrokeStyle};
 use crate::{PaintCtx, Widget};
 use smallvec::SmallVec;
+use std::sync::atomic::{AtomicU64, Ordering};
 use vello::Scene;
 use vello::kurbo::Shape;
-use vello::peniko::Mix;
 
 pub struct DrawContext<'a> {
     ctx: &'a mut PaintCtx,
@@ -73,9 +73,15 @@ impl<'a> DrawContext<'a> {
     }
 }
 
+/// Handed out one per unversioned [`Canvas`] paint, so its fingerprint never
+/// matches last frame's — see [`Canvas::version`]'s doc comment.
+static UNVERSIONED_CANVAS_TOKEN: AtomicU64 = AtomicU64::new(0);
+
 pub struct Canvas {
     pub size: Size,
     pub painter: Box<dyn FnMut(&mut DrawContext)>,
+    /// See [`Self::version`].
+    pub version: Option<u64>,
     scene: Scene, // Internal scene for recording
 }
 
@@ -84,6 +90,7 @@ impl Canvas {
         Self {
             size,
             painter: Box::new(painter),
+            version: None,
             scene: Scene::new(),
         }
     }
@@ -91,21 +98,35 @@ impl Canvas {
     pub fn get_scene(&self) -> &Scene {
         &self.scene
     }
+
+    /// A cheap stand-in fingerprint for `painter`'s drawn output, since the
+    /// closure itself can't be hashed: damage tracking considers this
+    /// canvas changed whenever `version` differs from last frame's. Leave
+    /// unset (the default) if `painter` draws something different every
+    /// frame (e.g. driven by elapsed time) — an unversioned canvas always
+    /// reports itself as changed, which is the same "always repaint" this
+    /// crate does today without damage tracking at all.
+    pub fn version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
 }
 
 impl Widget for Canvas {
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
         let rect = Rect::from_origin_size(Point::ORIGIN, self.size);
 
-        // Clip to the canvas size
-        // We must transform the local rect to global coordinates for the clip to work correctly
-        let global_clip = ctx.transform.transform_rect_bbox(rect);
-        scene.push_layer(Mix::Normal, 1.0, Affine::IDENTITY, &global_clip);
-
-        let mut draw_ctx = DrawContext::new(ctx, scene, self.size);
-        (self.painter)(&mut draw_ctx);
-
-        scene.pop_layer();
+        let fingerprint_seed = self
+            .version
+            .unwrap_or_else(|| UNVERSIONED_CANVAS_TOKEN.fetch_add(1, Ordering::Relaxed));
+        ctx.report_damage(rect, crate::damage::fingerprint(&[fingerprint_seed]));
+
+        let painter = &mut self.painter;
+        let size = self.size;
+        ctx.with_clip(rect, scene, |ctx, scene| {
+            let mut draw_ctx = DrawContext::new(ctx, scene, size);
+            (painter)(&mut draw_ctx);
+        });
     }
 
     fn children(&self) -> SmallVec<[crate::EntityId; 4]> {
@@ -118,6 +139,7 @@ use crate::element::Element;
 
 pub struct CanvasElement {
     size: Size,
+    version: Option<u64>,
     painter: Option<Box<dyn FnMut(&mut DrawContext)>>,
 }
 
@@ -125,6 +147,7 @@ impl CanvasElement {
     pub fn new(painter: impl FnMut(&mut DrawContext) + 'static) -> Self {
         Self {
             size: Size::ZERO,
+            version: None,
             painter: Some(Box::new(painter)),
         }
     }
@@ -133,12 +156,20 @@ impl CanvasElement {
         self.size = size;
         self
     }
+
+    /// See [`Canvas::version`].
+    pub fn version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
 }
 
 impl Element for CanvasElement {
     fn build(mut self: Box<Self>) -> Box<dyn Widget> {
         let painter = self.painter.take().unwrap();
-        Box::new(Canvas::new(self.size, painter))
+        let mut canvas = Canvas::new(self.size, painter);
+        canvas.version = self.version;
+        Box::new(canvas)
     }
 }
 
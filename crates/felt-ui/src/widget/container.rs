@@ -1,82 +1,200 @@
-use crate::draw::Color;
-use crate::{EntityId, PaintCtx, Widget};
+use crate::draw::{Brush, Color, Gradient, RoundedRect, RoundedRectRadii, StrokeStyle};
+use crate::layout::{self, LayoutStyle};
+use crate::{EntityId, Event, EventCtx, PaintCtx, Widget};
 use smallvec::SmallVec;
 use vello::Scene;
-use vello::kurbo::{Affine, Rect, Size, Stroke, Vec2};
-use vello::peniko::{Brush, Fill};
+use vello::kurbo::{Affine, Point, Rect, Size, Vec2};
+use vello::peniko::Fill;
 
 pub struct Container {
-    pub child: Option<Box<dyn Widget>>,
-    pub background: Option<Color>,
-    pub border: Option<(Color, f64)>, // Color, width
+    pub children: Vec<Box<dyn Widget>>,
+    pub background: Option<Brush>,
+    pub border: Option<(Brush, StrokeStyle)>,
+    pub corner_radii: RoundedRectRadii,
     pub offset: Vec2,
     pub size: Option<Size>,
+    pub layout_style: LayoutStyle,
 }
 
 impl Container {
     pub fn new() -> Self {
         Self {
-            child: None,
+            children: Vec::new(),
             background: None,
             border: None,
+            corner_radii: RoundedRectRadii::from_single_radius(0.0),
             offset: Vec2::ZERO,
             size: None,
+            layout_style: LayoutStyle::default(),
         }
     }
+
+    pub fn background(mut self, brush: impl Into<Brush>) -> Self {
+        self.background = Some(brush.into());
+        self
+    }
+
+    pub fn background_gradient(mut self, gradient: Gradient) -> Self {
+        self.background = Some(Brush::Gradient(gradient));
+        self
+    }
+
+    pub fn border(mut self, color: Color, width: f64) -> Self {
+        self.border = Some((Brush::Solid(color), StrokeStyle::new(width)));
+        self
+    }
+
+    /// Border with a full [`StrokeStyle`] (caps, joins, dashes) and any brush.
+    pub fn border_stroke(mut self, brush: impl Into<Brush>, style: StrokeStyle) -> Self {
+        self.border = Some((brush.into(), style));
+        self
+    }
+
+    /// Uniform corner radius on all four corners.
+    pub fn border_radius(mut self, radius: f64) -> Self {
+        self.corner_radii = RoundedRectRadii::from_single_radius(radius);
+        self
+    }
+
+    /// Per-corner radii, in `top_left, top_right, bottom_right, bottom_left` order.
+    pub fn border_radii(
+        mut self,
+        top_left: f64,
+        top_right: f64,
+        bottom_right: f64,
+        bottom_left: f64,
+    ) -> Self {
+        self.corner_radii = RoundedRectRadii::new(top_left, top_right, bottom_right, bottom_left);
+        self
+    }
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Widget for Container {
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
-        // Apply offset to transform
         let transform = ctx.transform.then_translate(self.offset);
 
-        let mut my_ctx = PaintCtx {
-            transform,
-            clip: ctx.clip,
+        // The size this container resolves to for its own background/border,
+        // and the available space its children are laid out within.
+        let resolved_size = self.size.unwrap_or(ctx.clip.size());
+
+        let local_rect = match self.size {
+            Some(size) => Rect::from_origin_size(Point::new(self.offset.x, self.offset.y), size),
+            None => ctx.clip,
         };
+        let fingerprint = crate::damage::fingerprint(&[
+            self.background.as_ref().map_or(u64::MAX, |b| b.fingerprint()),
+            self.border
+                .as_ref()
+                .map_or(u64::MAX, |(brush, style)| {
+                    crate::damage::fingerprint(&[brush.fingerprint(), style.fingerprint()])
+                }),
+            self.corner_radii.top_left.to_bits(),
+            self.corner_radii.top_right.to_bits(),
+            self.corner_radii.bottom_right.to_bits(),
+            self.corner_radii.bottom_left.to_bits(),
+        ]);
+        ctx.report_damage(local_rect, fingerprint);
 
         // Draw background if size is known
         if let Some(size) = self.size {
-            let rect = Rect::from_origin_size(vello::kurbo::Point::ORIGIN, size);
-
-            if let Some(color) = self.background {
-                // Convert Felt UI Color to Vello Color
-                let vello_color = vello::peniko::Color::rgba8(color.r, color.g, color.b, color.a);
-                scene.fill(
-                    Fill::NonZero,
-                    transform,
-                    &Brush::Solid(vello_color),
-                    None,
-                    &rect,
-                );
+            let rect = RoundedRect::from_rect(
+                Rect::from_origin_size(Point::ORIGIN, size),
+                self.corner_radii,
+            );
+
+            if let Some(brush) = &self.background {
+                scene.fill(Fill::NonZero, transform, &brush.to_vello(), None, &rect);
             }
 
-            if let Some((color, width)) = self.border {
-                // Convert Felt UI Color to Vello Color
-                let vello_color = vello::peniko::Color::rgba8(color.r, color.g, color.b, color.a);
-                scene.stroke(
-                    &Stroke::new(width),
-                    transform,
-                    &Brush::Solid(vello_color),
-                    None,
-                    &rect,
-                );
+            if let Some((brush, style)) = &self.border {
+                scene.stroke(&style.to_vello(), transform, &brush.to_vello(), None, &rect);
             }
-        } else if let Some(color) = self.background {
+        } else if let Some(brush) = &self.background {
             // If no size but background, fill the whole clip (Window background case)
-            let vello_color = vello::peniko::Color::rgba8(color.r, color.g, color.b, color.a);
             scene.fill(
                 Fill::NonZero,
                 Affine::IDENTITY,
-                &Brush::Solid(vello_color),
+                &brush.to_vello(),
                 None,
                 &ctx.clip,
             );
         }
 
-        if let Some(child) = &mut self.child {
-            child.paint(&mut my_ctx, scene);
+        if self.children.is_empty() {
+            return;
+        }
+
+        let child_styles: Vec<LayoutStyle> =
+            self.children.iter().map(|child| child.layout_style()).collect();
+        let (_root_rect, child_rects) =
+            layout::compute_layout(self.layout_style, &child_styles, resolved_size);
+
+        for (child, rect) in self.children.iter_mut().zip(child_rects.iter()) {
+            // Narrow the clip to the child's resolved flex cell, not just its
+            // origin — otherwise a child with no explicit `.size(...)` (the
+            // common case for `flex_size`/`width`/`height`) falls into its
+            // own `self.size == None` branch above and paints its background
+            // across the *entire* ambient clip instead of its allotted rect.
+            let local_rect = rect.with_origin(Point::new(
+                rect.x0 + self.offset.x,
+                rect.y0 + self.offset.y,
+            ));
+            let child_offset = self.offset + layout::rect_offset(rect);
+            ctx.with_clip(local_rect, scene, |ctx, scene| {
+                ctx.paint_child(child_offset, child.as_mut(), scene);
+            });
+        }
+    }
+
+    fn update(&mut self, dt: f64) {
+        for child in &mut self.children {
+            child.update(dt);
+        }
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) {
+        let previous_position = ctx.position;
+        let previous_bounds = ctx.bounds;
+        ctx.position = Point::new(
+            previous_position.x - self.offset.x,
+            previous_position.y - self.offset.y,
+        );
+
+        if !self.children.is_empty() {
+            let resolved_size = self.size.unwrap_or(previous_bounds);
+            let child_styles: Vec<LayoutStyle> =
+                self.children.iter().map(|child| child.layout_style()).collect();
+            let (_root_rect, child_rects) =
+                layout::compute_layout(self.layout_style, &child_styles, resolved_size);
+            let position_in_container = ctx.position;
+
+            for (child, rect) in self.children.iter_mut().zip(child_rects.iter()) {
+                if rect.contains(position_in_container) {
+                    ctx.position = Point::new(
+                        position_in_container.x - rect.x0,
+                        position_in_container.y - rect.y0,
+                    );
+                    ctx.bounds = Size::new(rect.width(), rect.height());
+                    child.on_event(ctx, event);
+                    if ctx.is_handled() {
+                        break;
+                    }
+                }
+            }
         }
+
+        ctx.position = previous_position;
+        ctx.bounds = previous_bounds;
+    }
+
+    fn layout_style(&self) -> LayoutStyle {
+        self.layout_style
     }
 
     fn children(&self) -> SmallVec<[EntityId; 4]> {
@@ -0,0 +1,280 @@
+//! WASM scripting ABI for dynamically loaded widgets, modeled on Canary's
+//! wasmtime harness: a guest module exports `build`/`update`/`paint` entry
+//! points, and [`ScriptWidget`] wraps an instance of it so scripted logic
+//! composes with ordinary Rust widgets under `Container`/`ScrollView`
+//! without the host needing to recompile when the script changes.
+//!
+//! `paint` is called with the widget's `(width, height)` and returns a
+//! packed `(ptr << 32) | len` pointing at a command buffer the guest wrote
+//! into its own linear memory. The buffer is a flat array of fixed-size,
+//! 32-byte records:
+//!
+//! ```text
+//! offset  0..4   tag    (0=FillRect 1=StrokeRect 2=PushClip 3=PopClip 4=DrawText)
+//! offset  4..8   x      (f32)
+//! offset  8..12  y      (f32)
+//! offset 12..16  w      (f32)
+//! offset 16..20  h      (f32)
+//! offset 20..24  color  (u32, 0xAABBGGRR)
+//! offset 24..28  text_ptr (u32, DrawText only)
+//! offset 28..32  text_len (u32, DrawText only)
+//! ```
+//!
+//! The host decodes this buffer and issues the matching `vello::Scene`
+//! calls — so from the guest's point of view it's drawing rects, clips, and
+//! text, but the boundary crossing is one flat buffer read rather than a
+//! host call per draw op. The only host import guests can call directly is
+//! `host.time_seconds`, for scripts that want wall-clock time rather than
+//! tracking accumulated `update(dt)` themselves.
+use crate::{EntityId, PaintCtx, Widget};
+use smallvec::SmallVec;
+use vello::Scene;
+use vello::kurbo::{Rect, Stroke};
+use vello::peniko::{Color, Fill, Mix};
+
+/// Wasmtime fuel units granted before each `build`/`update`/`paint` call, so
+/// a misbehaving or infinite-looping guest traps instead of hanging the
+/// render loop. Chosen generously for a single frame's worth of widget
+/// logic; a script that legitimately needs more should do its heavy lifting
+/// incrementally across frames rather than raising this further.
+const FUEL_PER_CALL: u64 = 10_000_000;
+
+const COMMAND_SIZE: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScriptError {
+    #[error("failed to compile WASM module: {0}")]
+    Compile(#[source] wasmtime::Error),
+    #[error("failed to instantiate WASM module: {0}")]
+    Instantiate(#[source] wasmtime::Error),
+    #[error("guest module is missing required export `{0}`")]
+    MissingExport(&'static str),
+    #[error("guest module has no exported memory named `memory`")]
+    MissingMemory,
+    #[error("guest trapped or ran out of fuel: {0}")]
+    Trap(#[source] wasmtime::Error),
+}
+
+/// Build an `Engine` configured for [`ScriptWidget`]: fuel metering enabled
+/// so `load` can bound each guest call. Cheap to clone (it's a handle around
+/// shared internal state) and meant to be created once and shared across
+/// every `ScriptWidget` instance in an app.
+pub fn script_engine() -> wasmtime::Engine {
+    let mut config = wasmtime::Config::new();
+    config.consume_fuel(true);
+    wasmtime::Engine::new(&config).expect("default wasmtime config is always valid")
+}
+
+/// Per-instance state threaded through the guest's `Store`, holding
+/// whatever the host ABI needs to answer guest imports.
+struct HostState {
+    elapsed_secs: f64,
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommandTag {
+    FillRect = 0,
+    StrokeRect = 1,
+    PushClip = 2,
+    PopClip = 3,
+    DrawText = 4,
+}
+
+impl CommandTag {
+    fn from_u32(tag: u32) -> Option<Self> {
+        Some(match tag {
+            0 => Self::FillRect,
+            1 => Self::StrokeRect,
+            2 => Self::PushClip,
+            3 => Self::PopClip,
+            4 => Self::DrawText,
+            _ => return None,
+        })
+    }
+}
+
+struct RawCommand {
+    tag: CommandTag,
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    color: u32,
+}
+
+fn decode_command(bytes: &[u8]) -> Option<RawCommand> {
+    if bytes.len() < COMMAND_SIZE {
+        return None;
+    }
+    Some(RawCommand {
+        tag: CommandTag::from_u32(u32::from_le_bytes(bytes[0..4].try_into().ok()?))?,
+        x: f32::from_le_bytes(bytes[4..8].try_into().ok()?),
+        y: f32::from_le_bytes(bytes[8..12].try_into().ok()?),
+        w: f32::from_le_bytes(bytes[12..16].try_into().ok()?),
+        h: f32::from_le_bytes(bytes[16..20].try_into().ok()?),
+        color: u32::from_le_bytes(bytes[20..24].try_into().ok()?),
+    })
+}
+
+fn color_from_packed(packed: u32) -> Color {
+    let [r, g, b, a] = packed.to_le_bytes();
+    Color::from_rgba8(r, g, b, a)
+}
+
+/// A [`Widget`] whose layout/paint/animation logic lives in a sandboxed WASM
+/// guest module rather than Rust. See the module docs for the host ABI.
+pub struct ScriptWidget {
+    store: wasmtime::Store<HostState>,
+    memory: wasmtime::Memory,
+    update_fn: wasmtime::TypedFunc<f64, ()>,
+    paint_fn: wasmtime::TypedFunc<(f32, f32), u64>,
+    /// Ordinary Rust widgets nested alongside the scripted one, so host-side
+    /// composition (e.g. a script-driven background under a native button)
+    /// doesn't require the guest to know about them.
+    children: Vec<Box<dyn Widget>>,
+}
+
+impl ScriptWidget {
+    /// Compile and instantiate `wasm_bytes` against `engine`, giving the
+    /// instance its own store and fuel budget. Calls the guest's `build`
+    /// export once before returning.
+    pub fn load(
+        engine: &wasmtime::Engine,
+        wasm_bytes: &[u8],
+        children: Vec<Box<dyn Widget>>,
+    ) -> Result<Self, ScriptError> {
+        let module = wasmtime::Module::new(engine, wasm_bytes).map_err(ScriptError::Compile)?;
+
+        let mut store = wasmtime::Store::new(engine, HostState { elapsed_secs: 0.0 });
+        store
+            .set_fuel(FUEL_PER_CALL)
+            .map_err(ScriptError::Instantiate)?;
+
+        let mut linker = wasmtime::Linker::new(engine);
+        linker
+            .func_wrap(
+                "host",
+                "time_seconds",
+                |caller: wasmtime::Caller<'_, HostState>| caller.data().elapsed_secs,
+            )
+            .map_err(ScriptError::Instantiate)?;
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(ScriptError::Instantiate)?;
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or(ScriptError::MissingMemory)?;
+        let build_fn = instance
+            .get_typed_func::<(), ()>(&mut store, "build")
+            .map_err(|_| ScriptError::MissingExport("build"))?;
+        let update_fn = instance
+            .get_typed_func::<f64, ()>(&mut store, "update")
+            .map_err(|_| ScriptError::MissingExport("update"))?;
+        let paint_fn = instance
+            .get_typed_func::<(f32, f32), u64>(&mut store, "paint")
+            .map_err(|_| ScriptError::MissingExport("paint"))?;
+
+        build_fn.call(&mut store, ()).map_err(ScriptError::Trap)?;
+
+        Ok(Self {
+            store,
+            memory,
+            update_fn,
+            paint_fn,
+            children,
+        })
+    }
+
+    /// Refill this instance's fuel before a guest call. Errors are ignored:
+    /// if refueling somehow fails the following call just traps, which
+    /// `paint`/`update` already treat as "skip this frame" rather than panic.
+    fn refuel(&mut self) {
+        let _ = self.store.set_fuel(FUEL_PER_CALL);
+    }
+}
+
+impl Widget for ScriptWidget {
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let size = ctx.clip.size();
+        self.refuel();
+        let Ok(packed) = self
+            .paint_fn
+            .call(&mut self.store, (size.width as f32, size.height as f32))
+        else {
+            // Guest trapped or ran out of fuel: skip its drawing this frame
+            // rather than taking down the whole render.
+            for child in &mut self.children {
+                child.paint(ctx, scene);
+            }
+            return;
+        };
+
+        let ptr = (packed >> 32) as u32 as usize;
+        let len = (packed & 0xFFFF_FFFF) as u32 as usize;
+        let data = self.memory.data(&self.store);
+
+        if let Some(buf) = data.get(ptr..ptr.saturating_add(len)) {
+            let mut clip_depth: u32 = 0;
+            for chunk in buf.chunks(COMMAND_SIZE) {
+                let Some(cmd) = decode_command(chunk) else {
+                    continue;
+                };
+                let rect = Rect::new(
+                    cmd.x as f64,
+                    cmd.y as f64,
+                    (cmd.x + cmd.w) as f64,
+                    (cmd.y + cmd.h) as f64,
+                );
+                let color = color_from_packed(cmd.color);
+                match cmd.tag {
+                    CommandTag::FillRect => {
+                        scene.fill(Fill::NonZero, ctx.transform, color, None, &rect);
+                    }
+                    CommandTag::StrokeRect => {
+                        scene.stroke(&Stroke::new(1.0), ctx.transform, color, None, &rect);
+                    }
+                    CommandTag::PushClip => {
+                        clip_depth += 1;
+                        scene.push_layer(Mix::Normal, 1.0, ctx.transform, &rect);
+                    }
+                    CommandTag::PopClip => {
+                        if clip_depth > 0 {
+                            clip_depth -= 1;
+                            scene.pop_layer();
+                        }
+                    }
+                    CommandTag::DrawText => {
+                        // Drawing text needs a shaped layout (see
+                        // `felt_platform::simple_text::SimpleText`), but
+                        // `felt-ui` doesn't depend on `felt-platform` and
+                        // `PaintCtx` doesn't thread a text context through —
+                        // dropped for now rather than faked.
+                    }
+                }
+            }
+            for _ in 0..clip_depth {
+                scene.pop_layer();
+            }
+        }
+
+        for child in &mut self.children {
+            child.paint(ctx, scene);
+        }
+    }
+
+    fn update(&mut self, dt: f64) {
+        self.store.data_mut().elapsed_secs += dt;
+        self.refuel();
+        let _ = self.update_fn.call(&mut self.store, dt);
+        for child in &mut self.children {
+            child.update(dt);
+        }
+    }
+
+    fn children(&self) -> SmallVec<[EntityId; 4]> {
+        SmallVec::new()
+    }
+}
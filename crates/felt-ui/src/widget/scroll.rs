@@ -1,20 +1,119 @@
-use crate::{EntityId, PaintCtx, Widget};
+use crate::{EntityId, Event, EventCtx, PaintCtx, Widget};
 use smallvec::SmallVec;
 use vello::Scene;
-use vello::kurbo::{Affine, Point, Rect, Vec2};
+use vello::kurbo::{Point, Rect, RoundedRect, RoundedRectRadii, Vec2};
 use vello::peniko::Mix;
 
+/// Time constant (seconds) of the exponential ease used to chase
+/// `target_offset`. Smaller settles faster; ~0.08s reads as "snappy but not
+/// instant".
+const SCROLL_TAU: f64 = 0.08;
+
+/// Below this many logical pixels of remaining distance, snap straight to
+/// `target_offset` instead of asymptotically crawling toward it forever.
+const SETTLE_EPSILON: f64 = 0.5;
+
+/// Drives a [`ScrollView`]'s smooth-scroll animation across frames.
+///
+/// `ScrollView` owns one of these and drives it from [`Widget::update`], so a
+/// retained `ScrollView` instance animates on its own once fed `scroll_by`/
+/// `scroll_to` calls. Code that instead rebuilds its widget tree from scratch
+/// every frame (immediate-mode style, via `scroll_view()`/`IntoElement`) has
+/// no retained `ScrollView` for `update` to be called on; that style of
+/// caller should keep its own `ScrollState` in its closure environment —
+/// the same way a `demo`-style app keeps an `Instant` around for time-based
+/// animation — and feed the eased `offset()` into `scroll_view().offset(..)`
+/// each frame instead.
+pub struct ScrollState {
+    rendered_offset: Vec2,
+    target_offset: Vec2,
+    viewport: Vec2,
+    content_size: Vec2,
+}
+
+impl ScrollState {
+    pub fn new(viewport: Vec2, content_size: Vec2) -> Self {
+        Self {
+            rendered_offset: Vec2::ZERO,
+            target_offset: Vec2::ZERO,
+            viewport,
+            content_size,
+        }
+    }
+
+    /// Update the known viewport/content extents, e.g. after a resize.
+    pub fn set_extents(&mut self, viewport: Vec2, content_size: Vec2) {
+        self.viewport = viewport;
+        self.content_size = content_size;
+        self.target_offset = self.clamp(self.target_offset);
+    }
+
+    fn clamp(&self, offset: Vec2) -> Vec2 {
+        let max_x = (self.content_size.x - self.viewport.x).max(0.0);
+        let max_y = (self.content_size.y - self.viewport.y).max(0.0);
+        Vec2::new(offset.x.clamp(0.0, max_x), offset.y.clamp(0.0, max_y))
+    }
+
+    /// Accumulate a wheel/trackpad delta into the scroll target, clamped to
+    /// `[0, content_size - viewport]` on each axis.
+    pub fn scroll_by(&mut self, delta: Vec2) {
+        self.target_offset = self.clamp(self.target_offset + delta);
+    }
+
+    /// Jump the target (and, on the next `tick`, the rendered offset) to an
+    /// absolute position.
+    pub fn scroll_to(&mut self, offset: Vec2) {
+        self.target_offset = self.clamp(offset);
+    }
+
+    /// Advance the rendered offset toward the target by one frame of
+    /// frame-rate-independent exponential smoothing. Returns `true` if the
+    /// animation is still in flight and the caller should keep redrawing
+    /// continuously; `false` once it has settled and on-demand redraws
+    /// suffice again.
+    pub fn tick(&mut self, dt: f64) -> bool {
+        let remaining = self.target_offset - self.rendered_offset;
+        if remaining.hypot() < SETTLE_EPSILON {
+            self.rendered_offset = self.target_offset;
+            return false;
+        }
+        let k = 1.0 - (-dt / SCROLL_TAU).exp();
+        self.rendered_offset += remaining * k;
+        true
+    }
+
+    /// The current rendered (eased) scroll offset, feed into `scroll_view().offset(..)`.
+    pub fn offset(&self) -> Vec2 {
+        self.rendered_offset
+    }
+
+    /// Scroll position normalized to `0.0..=1.0` on each axis, for drawing scrollbars.
+    pub fn normalized_position(&self) -> Vec2 {
+        let max_x = (self.content_size.x - self.viewport.x).max(0.0);
+        let max_y = (self.content_size.y - self.viewport.y).max(0.0);
+        Vec2::new(
+            if max_x > 0.0 { self.rendered_offset.x / max_x } else { 0.0 },
+            if max_y > 0.0 { self.rendered_offset.y / max_y } else { 0.0 },
+        )
+    }
+}
+
 pub struct ScrollView {
-    pub offset: Vec2,
+    pub state: ScrollState,
     pub size: Vec2,
+    /// Corner radii applied to the clip pushed around the viewport, so a
+    /// `ScrollView` nested inside a rounded [`Container`](crate::widget::container::Container)
+    /// clips its content to the same rounded shape instead of a plain rect.
+    pub corner_radii: RoundedRectRadii,
     pub child: Option<Box<dyn Widget>>,
 }
 
 impl ScrollView {
-    pub fn new(size: Vec2) -> Self {
+    pub fn new(size: Vec2, content_size: Vec2) -> Self {
         Self {
-            offset: Vec2::ZERO,
+            state: ScrollState::new(size, content_size),
             size,
+            corner_radii: RoundedRectRadii::from_single_radius(0.0),
             child: None,
         }
     }
@@ -23,33 +122,103 @@ impl ScrollView {
         self.child = Some(Box::new(child));
         self
     }
+
+    /// Uniform corner radius on the viewport clip.
+    pub fn corner_radius(mut self, radius: f64) -> Self {
+        self.corner_radii = RoundedRectRadii::from_single_radius(radius);
+        self
+    }
+
+    /// Accumulate a wheel/trackpad delta into the scroll target; see
+    /// [`ScrollState::scroll_by`].
+    pub fn scroll_by(&mut self, delta: Vec2) {
+        self.state.scroll_by(delta);
+    }
+
+    /// Jump the scroll target to an absolute position; see
+    /// [`ScrollState::scroll_to`].
+    pub fn scroll_to(&mut self, offset: Vec2) {
+        self.state.scroll_to(offset);
+    }
+
+    /// Scroll position normalized to `0.0..=1.0` on each axis, for drawing scrollbars.
+    pub fn normalized_position(&self) -> Vec2 {
+        self.state.normalized_position()
+    }
 }
 
 impl Widget for ScrollView {
+    fn update(&mut self, dt: f64) {
+        self.state.tick(dt);
+        if let Some(child) = &mut self.child {
+            child.update(dt);
+        }
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) {
+        let viewport = Rect::from_origin_size(Point::ORIGIN, (self.size.x, self.size.y));
+        if !viewport.contains(ctx.position) {
+            return;
+        }
+
+        // Forward to the child first, translated into scrolled content
+        // space, so the innermost widget gets first crack at the event —
+        // only if it leaves the event unhandled do we treat this view's own
+        // scrolling as the fallback.
+        if let Some(child) = &mut self.child {
+            let offset = self.state.offset();
+            let previous_position = ctx.position;
+            ctx.position = Point::new(
+                previous_position.x + offset.x,
+                previous_position.y + offset.y,
+            );
+            child.on_event(ctx, event);
+            ctx.position = previous_position;
+        }
+
+        if ctx.is_handled() {
+            return;
+        }
+
+        if let Event::Wheel { delta, .. } = event {
+            self.state.scroll_by(*delta);
+            ctx.set_handled();
+            ctx.request_repaint();
+        }
+    }
+
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
         let viewport = Rect::from_origin_size(Point::ORIGIN, (self.size.x, self.size.y));
 
         // 1. Calculate Global Clip Rect
-        // We transform the viewport rect by the current context transform to get the clip in scene coordinates.
-        // This avoids relying on push_layer's transform behavior for clipping, which might be subtle.
+        // We still need a plain bounding rect, in scene coordinates, to carry
+        // in the child's `PaintCtx::clip` — that field is a `Rect`, used as a
+        // coarse bounding box elsewhere (e.g. `Container`'s whole-clip fill),
+        // not the actual clip geometry.
         // Note: This assumes ctx.transform is only translation/scale, which it is.
-        // For rotation, we'd need a Shape transform, but Rect transform is fine here.
         let global_clip = ctx.transform.transform_rect_bbox(viewport);
 
         // 2. Push Clip Layer
-        // We use Identity transform for the layer, but provide the transformed clip rect.
-        scene.push_layer(Mix::Normal, 1.0, Affine::IDENTITY, &global_clip);
+        // The clip shape itself is a rounded rect (degenerating to a plain
+        // rect when `corner_radii` is zero), pushed with the real transform
+        // so rounded corners survive the translation into scene space.
+        let clip_shape = RoundedRect::from_rect(viewport, self.corner_radii);
+        scene.push_layer(Mix::Normal, 1.0, ctx.transform, &clip_shape);
 
         // 3. Paint Child with Manual Transform
-        // We pass the full transform (Parent * ScrollOffset) to the child.
+        // We pass the full transform (Parent * ScrollOffset) to the child,
+        // using the smoothed `ScrollState::offset()` rather than the raw,
+        // unsmoothed scroll target. `RoundedRect` clips aren't representable
+        // by `PaintCtx::with_clip` (which only accepts a plain `Rect`), so we
+        // keep the manual push/pop above but still route the child paint
+        // itself through `paint_child` for the transform composition, with
+        // `ctx.clip` narrowed to the viewport around that one call.
+        let offset = self.state.offset();
         if let Some(child) = &mut self.child {
-            let mut child_ctx = PaintCtx {
-                transform: ctx
-                    .transform
-                    .then_translate(Vec2::new(-self.offset.x, -self.offset.y)),
-                clip: global_clip,
-            };
-            child.paint(&mut child_ctx, scene);
+            let previous_clip = ctx.clip;
+            ctx.clip = global_clip;
+            ctx.paint_child(Vec2::new(-offset.x, -offset.y), child.as_mut(), scene);
+            ctx.clip = previous_clip;
         }
 
         // 4. Pop Clip Layer
@@ -66,7 +235,9 @@ use crate::element::{Element, IntoElement};
 
 pub struct ScrollViewElement {
     size: Vec2,
+    content_size: Option<Vec2>,
     offset: Vec2,
+    corner_radii: RoundedRectRadii,
     child: Option<Box<dyn Widget>>,
 }
 
@@ -74,7 +245,9 @@ impl ScrollViewElement {
     pub fn new() -> Self {
         Self {
             size: Vec2::ZERO,
+            content_size: None,
             offset: Vec2::ZERO,
+            corner_radii: RoundedRectRadii::from_single_radius(0.0),
             child: None,
         }
     }
@@ -84,6 +257,16 @@ impl ScrollViewElement {
         self
     }
 
+    /// The full scrollable extent of this view's content, used to clamp the
+    /// scroll offset. Defaults to `size` (no overflow) if left unset.
+    pub fn content_size(mut self, content_size: Vec2) -> Self {
+        self.content_size = Some(content_size);
+        self
+    }
+
+    /// Set the initial scroll offset directly, bypassing the easing
+    /// animation. Later `scroll_by`/`scroll_to` calls on the built
+    /// `ScrollView` animate from here as usual.
     pub fn offset(mut self, offset: Vec2) -> Self {
         self.offset = offset;
         self
@@ -93,12 +276,24 @@ impl ScrollViewElement {
         self.child = Some(child.into_element().build());
         self
     }
+
+    /// Uniform corner radius on the viewport clip, matching a rounded parent
+    /// [`Container`](crate::widget::container::Container).
+    pub fn corner_radius(mut self, radius: f64) -> Self {
+        self.corner_radii = RoundedRectRadii::from_single_radius(radius);
+        self
+    }
 }
 
 impl Element for ScrollViewElement {
     fn build(self: Box<Self>) -> Box<dyn Widget> {
-        let mut sv = ScrollView::new(self.size);
-        sv.offset = self.offset;
+        let content_size = self.content_size.unwrap_or(self.size);
+        let mut sv = ScrollView::new(self.size, content_size);
+        // Place directly, skipping the easing animation: the caller set an
+        // absolute starting position, not a delta to chase toward.
+        sv.state.scroll_to(self.offset);
+        sv.state.rendered_offset = sv.state.target_offset;
+        sv.corner_radii = self.corner_radii;
         if let Some(child) = self.child {
             sv.child = Some(child);
         }
@@ -0,0 +1,27 @@
+//! Exercises `felt_ui::reftest::run_reftest` end-to-end against a real
+//! fixture, so the harness itself has test coverage rather than just being
+//! callable.
+use felt_ui::reftest::run_reftest;
+
+#[test]
+fn solid_fill_matches_reference() {
+    pollster::block_on(run_reftest(
+        "tests/fixtures/solid_fill.ron",
+        "tests/fixtures/solid_fill.png",
+        0,
+    ))
+    .unwrap();
+}
+
+/// Two `flex_size`-percent children side by side — guards against
+/// `Container::paint` painting an unsized child's background across the
+/// whole parent rect instead of clipping it to its resolved flex cell.
+#[test]
+fn split_flex_matches_reference() {
+    pollster::block_on(run_reftest(
+        "tests/fixtures/split_flex.ron",
+        "tests/fixtures/split_flex.png",
+        0,
+    ))
+    .unwrap();
+}
@@ -1,5 +1,10 @@
 use felt_platform::App;
-use felt_ui::{canvas, div, scroll_view, AppExtension, IntoElement, PaintCtx, Widget};
+use felt_ui::{
+    canvas, div, dispatch_event, scroll_view, translate_input_event, IntoElement, LayerRenderer,
+    PaintCtx, Widget,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::time::Instant;
 use vello::kurbo::{Affine, Point, Rect, Size, Vec2};
 use vello::peniko::{Brush, Color, Fill, Mix};
@@ -14,14 +19,11 @@ fn main() {
 
     println!("Running PoC V4 - 3 Distinct Layers");
 
-    app.mount_ui(move || {
-        // For every frame, transfirm scroll offset automatically
-        // In the future we will use gesture events to control this
-        let t = start_time.elapsed().as_secs_f64();
-        let scroll_offset_y = (t * 1.0).sin() * 400.0 + 400.0;
-
-        // Rebuild the widget tree every frame (Declarative Style!)
-        // Now the entire scene is described as a widget tree.
+    // Built once and retained across frames (rather than `mount_ui`'s usual
+    // rebuild-every-frame style) so the `scroll_view`'s `ScrollState` keeps
+    // chasing its target offset across both paint and wheel-event dispatch —
+    // scrolling is now driven by real input instead of a hardcoded sine wave.
+    let root_widget: Rc<RefCell<Box<dyn Widget>>> = Rc::new(RefCell::new(
         div()
             .bg(Color::rgb8(10, 10, 10)) // Window Background
             .child(
@@ -33,7 +35,7 @@ fn main() {
                     .child(
                         scroll_view()
                             .size(Vec2::new(600.0, 400.0))
-                            .offset(Vec2::new(0.0, scroll_offset_y))
+                            .content_size(Vec2::new(600.0, 1200.0))
                             .child(
                                 div() // LAYER 2: SCROLL PANEL (The moving surface)
                                     .size(Size::new(500.0, 1200.0))
@@ -45,6 +47,8 @@ fn main() {
                                             .bg(Color::rgb8(60, 60, 100)) // Blue-ish Canvas Background
                                             .child(
                                                 canvas(move |ctx, scene| {
+                                                    let t = start_time.elapsed().as_secs_f64();
+
                                                     // Draw diagonal stripes
                                                     for i in 0..22 {
                                                         let y = i as f64 * 50.0;
@@ -94,15 +98,52 @@ fn main() {
                                                     }
                                                     scene.pop_layer();
                                                 })
-                                                .size(Size::new(400.0, 1100.0))
-                                            )
+                                                .size(Size::new(400.0, 1100.0)),
+                                            ),
                                     )
                                     // We need to wrap the canvas in a div to offset it to (50, 50)
                                     // But `div().child(canvas)` works.
-                                )
-                            )
-                    )
-                });
+                            ),
+                    ),
+            )
+            .into_element()
+            .build(),
+    ));
+
+    let mut last_frame = Instant::now();
+    let paint_widget = Rc::clone(&root_widget);
+    app.mount(move |scene: &mut Scene, width, height, layer_renderer| {
+        let now = Instant::now();
+        let dt = now.duration_since(last_frame).as_secs_f64();
+        last_frame = now;
+
+        let mut widget = paint_widget.borrow_mut();
+        widget.update(dt);
+
+        let layer_renderer: Option<Rc<dyn LayerRenderer>> =
+            layer_renderer.map(|handle| Rc::new(handle) as Rc<dyn LayerRenderer>);
+        let mut ctx = PaintCtx {
+            transform: Affine::IDENTITY,
+            clip: Rect::new(0.0, 0.0, width as f64, height as f64),
+            layer_renderer,
+            // This demo doesn't opt into damage tracking (see `mount_ui` for
+            // that), and animates every frame regardless, so it always
+            // requests the next one below.
+            damage: None,
+        };
+        widget.paint(&mut ctx, scene);
+
+        true
+    });
+
+    // The window's default inner size (winit's `WindowBuilder::new()`
+    // default) — only used to seed the hit-test root's bounds, which this
+    // tree never falls back on since every container sets an explicit size.
+    let input_widget = Rc::clone(&root_widget);
+    app.on_input(move |input| {
+        let event = translate_input_event(input);
+        dispatch_event(input_widget.borrow_mut().as_mut(), 800, 600, &event);
+    });
 
     app.run();
 }